@@ -35,21 +35,20 @@ use imports::*;
 /// registers directly as such in order to gain access to the advanced features you need to enable the `advanced` feature in your `Cargo.toml` file
 #[cfg(feature = "advanced")]
 fn main() {
+    use core::time::Duration;
     use utils::setup_delay;
 
     let i2c = setup_i2c(1_000).expect("Failed to set up I2C");
     let delay = setup_delay();
     let mut ap33772s = Ap33772s::new(i2c, delay);
 
-    // Read The Status Register
-    let status = ap33772s.get_status().expect("Failed to get status");
-    println!("Status: {:?}", status);
-
-    if status.started() && status.i2c_ready() {
-        println!(" AP33772S Is up and running!")
-    } else {
-        println!("Status: {}", status);
-        println!("AP33772S is not ready..., but I2C communication is working");
+    // Wait for the device to report that it has started and is ready for I2C commands
+    match ap33772s.wait_until_ready(Duration::from_millis(500), false) {
+        Ok(status) => println!(" AP33772S Is up and running! {}", status),
+        Err(error) => {
+            println!("{}", error);
+            println!("AP33772S is not ready..., but I2C communication is working");
+        }
     }
 
     println!("Apply Startup Settings:");
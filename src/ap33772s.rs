@@ -18,6 +18,7 @@ use core::time::Duration;
 use super::hal::*;
 use crate::commands::configuration::system_control::SystemControl;
 use crate::commands::power_delivery::power_delivery_command_message::PowerDeliveryCommandMessage;
+use crate::setters::LockedConfiguration;
 
 // Public API Types
 use crate::types::command_structures::*;
@@ -42,7 +43,7 @@ use crate::units::*;
 /// ```rust
 /// use ap33772s_rs::Ap33772s;
 /// use ap33772s_rs::units::*;
-/// # async fn example(i2c: impl embedded_hal::i2c::I2c, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
 /// // Create and initialize with default settings
 /// let mut device = Ap33772s::new_default(i2c, delay)?;
 ///
@@ -58,7 +59,7 @@ use crate::units::*;
 /// ```rust
 /// use ap33772s_rs::{Ap33772s, types::{ThermalResistances, Thresholds}};
 ///
-/// # async fn example(i2c: impl embedded_hal::i2c::I2c, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
 /// // Create device without initialization
 /// let mut device = Ap33772s::new(i2c, delay);
 ///
@@ -84,6 +85,22 @@ pub struct Ap33772s<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: Inpu
     /// The InputPin assigned for the Interrupt signal. This pin will go high when the AP33772S is ready for communication
     #[cfg(feature = "interrupts")]
     pub(crate) interrupt_pin: P,
+    /// When enabled, configuration setters read each register back after writing it
+    pub(crate) write_verification: bool,
+    /// When set, the OVP and OCP thresholds are recalculated for every negotiated contract
+    pub(crate) threshold_tracking: Option<ThresholdTracking>,
+    /// When set, current requests and the over current threshold are capped to the cable rating
+    pub(crate) cable_rating: Option<CableRating>,
+    /// When set, requests and the over current threshold above these limits are refused
+    pub(crate) safety_limits: Option<SafetyLimits>,
+    /// When set, the safety relevant registers cannot be written and hold these values
+    pub(crate) locked_configuration: Option<LockedConfiguration>,
+    /// How failed I2C transfers are retried
+    pub(crate) retry_policy: RetryPolicy,
+    /// The number of I2C transfers that have been retried
+    pub(crate) retry_count: u32,
+    /// Whether statistics are read in a single transfer
+    pub(crate) statistics_burst_read: BurstRead,
 }
 
 /// This impl block represents the the initialisation methods for when no interrupts are used. This approach uses a
@@ -91,7 +108,9 @@ pub struct Ap33772s<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: Inpu
 #[cfg(not(feature = "interrupts"))]
 impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
     const NEGOTIATE_TIMING_DELAY: Duration = Duration::from_millis(100);
-    const BOOT_UP_DELAY: Duration = Duration::from_millis(100);
+    const BOOT_UP_TIMEOUT: Duration = Duration::from_millis(100);
+    /// The interval between STATUS reads in [`Self::wait_until_ready`]
+    pub const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(5);
     /// Creates a new AP33772S driver instance without performing any initialization.
     ///
     /// This method only creates the driver struct with the provided I2C and delay interfaces.
@@ -115,7 +134,18 @@ impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
     ///
     /// For automatic initialization with default settings, use [`Self::new_default`] instead.
     pub fn new(i2c: I2C, delay: D) -> Self {
-        Self { i2c, delay }
+        Self {
+            i2c,
+            delay,
+            write_verification: false,
+            threshold_tracking: None,
+            cable_rating: None,
+            safety_limits: None,
+            locked_configuration: None,
+            retry_policy: RetryPolicy::default(),
+            retry_count: 0,
+            statistics_burst_read: BurstRead::Detect,
+        }
     }
     /// Creates and initializes a new AP33772S driver with default configuration.
     ///
@@ -135,20 +165,22 @@ impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
     /// Returns `Ok(Ap33772s)` if initialization succeeds, or an [`Ap33772sError`] if:
     /// - Device is not present on the I2C bus
     /// - Device is not in the correct boot state
+    /// - Device does not become ready after the hard reset, see [`Ap33772sError::ReadyTimeout`]
     /// - Communication errors occur during setup
     ///
     /// # Important Notes
     ///
     /// ⚠️ **This method must be called immediately after device power-on.** If called on an already
     /// initialized device, it may return [`Ap33772sError::InitialisationFailure`]. In such cases,
-    /// a full power cycle of the device is required before retrying.
+    /// a full power cycle of the device is required before retrying. Use [`Self::attach`] to continue
+    /// with a device that may already be running.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use ap33772s_rs::{Ap33772s, types::Ap33772sError};
     ///
-    /// # async fn example(i2c: impl embedded_hal::i2c::I2c, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
     /// // Initialize device with default settings
     /// let mut device = Ap33772s::new_default(i2c, delay)?;
     ///
@@ -164,33 +196,295 @@ impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     /// [`Ap33772sError::InitialisationFailure`]: crate::errors::Ap33772sError::InitialisationFailure
     #[maybe_async::maybe_async]
-    pub async fn new_default(i2c: I2C, delay: D) -> Result<Self, Ap33772sError> {
-        let mut device = Self::new(i2c, delay);
+    pub async fn new_default(i2c: I2C, delay: D) -> Result<Self, Ap33772sError<I2C::Error>> {
+        Self::initialise_default(Self::new(i2c, delay)).await
+    }
+
+    /// Creates and initializes a new AP33772S driver instance like [`Self::new_default`], with
+    /// [`SafetyLimits`] that are applied before anything is written.
+    ///
+    /// The default thresholds written during initialization are checked against the limits, so
+    /// limits stricter than the register defaults return [`Ap33772sError::SafetyLimitExceeded`]
+    /// instead of writing thresholds the application would refuse. See
+    /// [`Self::with_safety_limits`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ap33772s_rs::{Ap33772s, types::SafetyLimits, units::*};
+    ///
+    /// # fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut device = Ap33772s::new_default_with_limits(
+    ///     i2c,
+    ///     delay,
+    ///     SafetyLimits {
+    ///         maximum_voltage: ElectricPotential::new::<volt>(12.0),
+    ///         ..SafetyLimits::default()
+    ///     },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError::SafetyLimitExceeded`]: crate::errors::Ap33772sError::SafetyLimitExceeded
+    #[maybe_async::maybe_async]
+    pub async fn new_default_with_limits(
+        i2c: I2C,
+        delay: D,
+        limits: SafetyLimits,
+    ) -> Result<Self, Ap33772sError<I2C::Error>> {
+        Self::initialise_default(Self::new(i2c, delay).with_safety_limits(limits)).await
+    }
+
+    #[maybe_async::maybe_async]
+    async fn initialise_default(mut device: Self) -> Result<Self, Ap33772sError<I2C::Error>> {
         device.is_device_present().await?;
 
-        let device_status = device.get_status().await?;
-        if device_status.i2c_ready()
-            && device_status.started()
-            && device_status.new_power_data_object()
-        {
+        let (fresh_boot, _) = device.read_boot_status().await?;
+        if fresh_boot {
             Self::initialise(&mut device).await?;
         } else {
             // Device May already be initialised, to do a fresh install, the user must fully power cycle the device
             device.hard_reset().await?; // This does not fully power cycle the RotoPD board due to the device being powered by the STEMMA connector
+            device
+                .wait_until_ready(Self::BOOT_UP_TIMEOUT, false)
+                .await?;
             Self::initialise(&mut device).await?;
             return Err(Ap33772sError::InitialisationFailure);
         }
         Ok(device)
     }
+
+    /// Creates a driver for a device that may already be running, without resetting it.
+    ///
+    /// If the device is fresh from power-on it is initialised exactly like [`Self::new_default`].
+    /// Otherwise, for example after the microcontroller was reset by a watchdog or a firmware
+    /// update while USB power stayed on, nothing is written: the negotiated contract and the
+    /// existing configuration are kept and read back into a [`DeviceSnapshot`].
+    ///
+    /// # Returns
+    ///
+    /// The driver and a [`DeviceSnapshot`] of the device configuration, or an [`Ap33772sError`] if
+    /// the device is not present or a read fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ap33772s_rs::Ap33772s;
+    /// use ap33772s_rs::units::*;
+    ///
+    /// # fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+    /// let (mut device, snapshot) = Ap33772s::attach(i2c, delay)?;
+    /// if snapshot.adopted {
+    ///     println!(
+    ///         "Resumed with a {:.1}V contract",
+    ///         snapshot.requested_voltage.get::<volt>()
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    async fn initialise(device: &mut Self) -> Result<(), Ap33772sError> {
-        device
-            .delay
-            .delay_ms(
-                u32::try_from(Self::BOOT_UP_DELAY.as_millis())
-                    .expect("This should not fail, HAL Duration Type Conversions"),
-            )
-            .await; // Initial delay to allow the device to power up
+    pub async fn attach(
+        i2c: I2C,
+        delay: D,
+    ) -> Result<(Self, DeviceSnapshot), Ap33772sError<I2C::Error>> {
+        let mut device = Self::new(i2c, delay);
+        device.is_device_present().await?;
+
+        let (fresh_boot, status) = device.read_boot_status().await?;
+        let adopted = !fresh_boot;
+        if !adopted {
+            Self::initialise(&mut device).await?;
+        }
+        let snapshot = DeviceSnapshot {
+            adopted,
+            status,
+            power_delivery_mode: device.get_power_delivery_configuration().await?,
+            thresholds: device.get_thresholds().await?,
+            thermal_resistances: device.get_thermal_resistances().await?,
+            minimum_selection_voltage: device.get_minimum_selection_voltage().await?,
+            requested_voltage: device.get_requested_voltage().await?,
+            requested_current: device.get_requested_current().await?,
+        };
+        Ok((device, snapshot))
+    }
+
+    /// Polls STATUS until the device has started, is ready for I2C commands and, when
+    /// `require_new_power_data_object` is set, has received the source capabilities.
+    ///
+    /// STATUS is cleared every time it is read, so the flags seen on each poll are accumulated and
+    /// each only needs to have been set once. STATUS is read every [`Self::STATUS_POLL_INTERVAL`]
+    /// and the timeout counts only the delays between reads, not the time spent on the bus.
+    ///
+    /// # Returns
+    ///
+    /// The accumulated [`Status`] once the flags are set, [`Ap33772sError::ReadyTimeout`] with
+    /// the flags seen if the timeout elapses first, or [`Ap33772sError`] on communication error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::Ap33772s;
+    /// # use core::time::Duration;
+    /// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let status = device.wait_until_ready(Duration::from_millis(500), true)?;
+    /// println!("AP33772S is up and running: {status}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`Ap33772sError::ReadyTimeout`]: crate::errors::Ap33772sError::ReadyTimeout
+    #[maybe_async::maybe_async]
+    pub async fn wait_until_ready(
+        &mut self,
+        timeout: Duration,
+        require_new_power_data_object: bool,
+    ) -> Result<Status, Ap33772sError<I2C::Error>> {
+        let mut flags_seen = 0x00;
+        let mut waited = Duration::ZERO;
+        loop {
+            flags_seen |= self.get_status().await?.raw_value();
+            let status = Status::new_with_raw_value(flags_seen);
+            if status.started()
+                && status.i2c_ready()
+                && (status.new_power_data_object() || !require_new_power_data_object)
+            {
+                return Ok(status);
+            }
+            if waited >= timeout {
+                return Err(Ap33772sError::ReadyTimeout(status));
+            }
+            self.delay
+                .delay_us(
+                    u32::try_from(Self::STATUS_POLL_INTERVAL.as_micros())
+                        .expect("This should not fail, HAL Duration Type Conversions"),
+                )
+                .await;
+            waited += Self::STATUS_POLL_INTERVAL;
+        }
+    }
+
+    /// Makes the EPR PDOs 8 to 13 available, enabling EPR Mode in PDCONFIG if needed and waiting
+    /// for the source to send its EPR capabilities.
+    ///
+    /// The AP33772S tries to enter EPR Mode after a negotiation with a source that supports it,
+    /// as long as EPR_MODE_EN is set. With the power-on default this happens right after the first
+    /// negotiation. If EPR was disabled during that negotiation, a request has to be sent after
+    /// calling this before the EPR capabilities arrive.
+    ///
+    /// EPR Mode ends with a hard reset or a new attach, after which the source only offers its
+    /// SPR PDOs and EPR Mode has to be entered again. The USB PD specification also has the
+    /// source hard reset an EPR sink that stops sending EPR_KeepAlive messages. There is no
+    /// register for these messages, so the driver cannot send them. Use
+    /// [`Self::check_extended_power_range`] to detect that EPR Mode was left.
+    ///
+    /// STATUS is read every [`Self::STATUS_POLL_INTERVAL`] while waiting, which clears the
+    /// protection flags it also carries.
+    ///
+    /// # Returns
+    ///
+    /// The source capabilities including the EPR PDOs,
+    /// [`Ap33772sError::ExtendedPowerRangeNotEntered`] if they have not arrived before the timeout,
+    /// or [`Ap33772sError`] on communication error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::Ap33772s;
+    /// # use ap33772s_rs::types::command_structures::*;
+    /// # use core::time::Duration;
+    /// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pdos = device.enter_extended_power_range(Duration::from_millis(500))?;
+    /// device.negotiate_maximum_power_delivery(PowerDataObject::ExtendedPowerRange8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`Ap33772sError::ExtendedPowerRangeNotEntered`]: crate::errors::Ap33772sError::ExtendedPowerRangeNotEntered
+    #[maybe_async::maybe_async]
+    pub async fn enter_extended_power_range(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<AllSourceDataPowerDataObject, Ap33772sError<I2C::Error>> {
+        let data_objects = self.get_all_source_power_capabilities().await?;
+        if data_objects.extended_power_range_entered() {
+            return Ok(data_objects);
+        }
+        let power_delivery_mode = self.get_power_delivery_configuration().await?;
+        if !power_delivery_mode.extended_power_range_mode_enabled {
+            self.set_power_delivery_mode(PowerDeliveryMode {
+                extended_power_range_mode_enabled: true,
+                ..power_delivery_mode
+            })
+            .await?;
+        }
+
+        let mut waited = Duration::ZERO;
+        loop {
+            if self.get_status().await?.new_power_data_object() {
+                let data_objects = self.get_all_source_power_capabilities().await?;
+                if data_objects.extended_power_range_entered() {
+                    return Ok(data_objects);
+                }
+            }
+            if waited >= timeout {
+                return Err(Ap33772sError::ExtendedPowerRangeNotEntered);
+            }
+            self.delay
+                .delay_us(
+                    u32::try_from(Self::STATUS_POLL_INTERVAL.as_micros())
+                        .expect("This should not fail, HAL Duration Type Conversions"),
+                )
+                .await;
+            waited += Self::STATUS_POLL_INTERVAL;
+        }
+    }
+
+    /// Checks that the AP33772S is still in EPR Mode by reading the source capabilities again.
+    ///
+    /// The EPR PDOs disappear from the capabilities when EPR Mode is left, e.g. after a hard
+    /// reset or a new attach. Call [`Self::enter_extended_power_range`] to enter it again.
+    ///
+    /// # Returns
+    ///
+    /// The source capabilities including the EPR PDOs,
+    /// [`Ap33772sError::ExtendedPowerRangeNotEntered`] if EPR Mode was left, or [`Ap33772sError`]
+    /// on communication error.
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`Ap33772sError::ExtendedPowerRangeNotEntered`]: crate::errors::Ap33772sError::ExtendedPowerRangeNotEntered
+    #[maybe_async::maybe_async]
+    pub async fn check_extended_power_range(
+        &mut self,
+    ) -> Result<AllSourceDataPowerDataObject, Ap33772sError<I2C::Error>> {
+        let data_objects = self.get_all_source_power_capabilities().await?;
+        if data_objects.extended_power_range_entered() {
+            Ok(data_objects)
+        } else {
+            Err(Ap33772sError::ExtendedPowerRangeNotEntered)
+        }
+    }
+
+    /// Returns `true` if the device is fresh from power-on, with every STATUS flag seen while
+    /// polling. The STARTED, I2C_READY and NEWPDO flags are cleared when STATUS is read, so they
+    /// are only all seen while the device is booting. The reads also clear any latched UVP, OVP,
+    /// OCP or OTP flags, which are only reported in the returned [`Status`].
+    #[maybe_async::maybe_async]
+    async fn read_boot_status(&mut self) -> Result<(bool, Status), Ap33772sError<I2C::Error>> {
+        match self.wait_until_ready(Self::BOOT_UP_TIMEOUT, true).await {
+            Ok(status) => Ok((true, status)),
+            Err(Ap33772sError::ReadyTimeout(status)) => Ok((false, status)),
+            Err(other) => Err(other),
+        }
+    }
+
+    #[maybe_async::maybe_async]
+    async fn initialise(device: &mut Self) -> Result<(), Ap33772sError<I2C::Error>> {
         device
             .set_thermal_resistances(ThermalResistances::default())
             .await?;
@@ -201,6 +495,17 @@ impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
     /// It does include a delay in which the result will be read from the device. The delay is handled
     /// by the hal provided. If the user wishes to ignore this delay, they should use the
     /// driver in `advanced` mode by enabled the `advanced` feature.
+    ///
+    /// When a [`CableRating`] is set, a current above the rating is lowered to the rating before
+    /// the request is sent. See [`Self::set_cable_rating`].
+    ///
+    /// When [`ThresholdTracking`] is enabled, the over voltage and over current thresholds are
+    /// loosened for the requested contract before the request is sent and tightened to the
+    /// negotiated contract once it is accepted. See [`Self::set_threshold_tracking`].
+    ///
+    /// The request is checked with [`PowerRequest::validate`] and against the [`SafetyLimits`]
+    /// first. PDOs 8 to 13 can only be requested once EPR Mode has been entered, see
+    /// [`Self::enter_extended_power_range`].
     #[maybe_async::maybe_async]
     pub async fn negotiate_power_delivery(
         &mut self,
@@ -208,42 +513,121 @@ impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
         voltage_selection: Option<ElectricPotential>,
         current_selection: OperatingCurrentSelection,
         data_objects: &AllSourceDataPowerDataObject,
-    ) -> Result<PowerDeliveryResponse, Ap33772sError> {
-        // Check to see if PDO requested is available on the Source, return early if not
-        if !data_objects
-            .get_power_data_object(power_data_object_index)
-            .is_detected()
-        {
-            return Err(Ap33772sError::PowerDataObjectNotDetected(
-                power_data_object_index,
-            ));
-        }
-
-        self.send_power_delivery_request(
+    ) -> Result<PowerDeliveryResponse, Ap33772sError<I2C::Error>> {
+        let current_selection = self.cap_current_selection(current_selection);
+        let data_object = data_objects.get_power_data_object(power_data_object_index);
+        // Check the request against the Source before any threshold is changed, return early if invalid
+        self.checked_power_request(
             power_data_object_index,
             voltage_selection,
             current_selection,
             data_objects,
-        )
-        .await?;
-        self.delay
-            .delay_ms(
-                u32::try_from(Self::NEGOTIATE_TIMING_DELAY.as_millis())
-                    .expect("This should not fail, HAL Duration Type Conversions"),
+        )?;
+
+        let previous_thresholds = match self.threshold_tracking {
+            Some(tracking) => {
+                let voltage = match voltage_selection {
+                    Some(voltage) if data_object.source_power_type() != PowerType::Fixed => voltage,
+                    _ => data_object.get_max_voltage()?,
+                };
+                // A 5A PDO reports the open ended range above 5A, which CURRENT_SEL cannot exceed
+                let current = if current_selection == OperatingCurrentSelection::Maximum {
+                    data_object
+                        .get_max_current()
+                        .max_range()
+                        .min(OperatingCurrentSelection::Maximum.current())
+                } else {
+                    current_selection.current()
+                };
+                let current = self.cap_over_current(current);
+                Some(
+                    self.loosen_tracked_thresholds(tracking, voltage, current)
+                        .await?,
+                )
+            }
+            None => None,
+        };
+
+        let response = match self
+            .send_power_delivery_request(
+                power_data_object_index,
+                voltage_selection,
+                current_selection,
+                data_objects,
             )
-            .await;
-        self.get_power_delivery_request_result().await
+            .await
+        {
+            Ok(()) => self.wait_for_power_delivery_request_result().await,
+            Err(error) => Err(error),
+        };
+        match (self.threshold_tracking, previous_thresholds) {
+            (Some(tracking), Some(previous)) => {
+                self.finish_threshold_tracking(tracking, &previous, response)
+                    .await
+            }
+            _ => response,
+        }
     }
 
     /// Performs a negotiation with the AP33772S device to change its current state to the configuration provided. Uses the `self.delay` to
     /// wait for the response. Wether the delay is blocking or not is dependent on HAL thats implements the `Delay` trait.
+    ///
+    /// When a [`CableRating`] is set and the PDO offers more current than the cable is rated for,
+    /// the rating is requested instead of the maximum current. This reads the source capabilities
+    /// first.
+    ///
+    /// When [`SafetyLimits`] are set, the maximum voltage and current of the PDO are checked
+    /// against them before any threshold is changed or the request is sent. The AP33772S only reports the maximum current of
+    /// a PDO as a 250mA range, the top of the range is checked.
+    ///
+    /// When [`ThresholdTracking`] is enabled, the PDO is read and the over voltage and over current
+    /// thresholds are loosened to its maximum voltage and current before the request is sent, then
+    /// tightened to the negotiated contract once it is accepted.
     #[maybe_async::maybe_async]
     pub async fn negotiate_maximum_power_delivery(
         &mut self,
         power_data_object_index: PowerDataObject,
-    ) -> Result<PowerDeliveryResponse, Ap33772sError> {
-        self.send_maximum_power_delivery_request(power_data_object_index)
+    ) -> Result<PowerDeliveryResponse, Ap33772sError<I2C::Error>> {
+        // Check the request before any threshold is changed, return early if refused
+        let delivery_message = self
+            .checked_maximum_power_request(power_data_object_index)
             .await?;
+        let previous_thresholds = match self.threshold_tracking {
+            Some(tracking) => {
+                let data_object = self
+                    .get_source_power_capability(power_data_object_index)
+                    .await?;
+                let voltage = data_object.get_max_voltage()?;
+                let current = data_object
+                    .get_max_current()
+                    .max_range()
+                    .min(OperatingCurrentSelection::Maximum.current());
+                let current = self.cap_over_current(current);
+                Some(
+                    self.loosen_tracked_thresholds(tracking, voltage, current)
+                        .await?,
+                )
+            }
+            None => None,
+        };
+        let response = match self.write_two_byte_command(delivery_message).await {
+            Ok(()) => self.wait_for_power_delivery_request_result().await,
+            Err(error) => Err(error),
+        };
+        match (self.threshold_tracking, previous_thresholds) {
+            (Some(tracking), Some(previous)) => {
+                self.finish_threshold_tracking(tracking, &previous, response)
+                    .await
+            }
+            _ => response,
+        }
+    }
+
+    /// Waits for the device to complete a negotiation and reads the result
+    #[maybe_async::maybe_async]
+    async fn wait_for_power_delivery_request_result(
+        &mut self,
+    ) -> Result<PowerDeliveryResponse, Ap33772sError<I2C::Error>> {
         self.delay
             .delay_ms(
                 u32::try_from(Self::NEGOTIATE_TIMING_DELAY.as_millis())
@@ -266,12 +650,24 @@ impl<I2C: I2c, D: DelayNs, P: InputPin> Ap33772s<I2C, D, P> {
             i2c,
             delay,
             interrupt_pin,
+            write_verification: false,
+            threshold_tracking: None,
+            cable_rating: None,
+            safety_limits: None,
+            locked_configuration: None,
+            retry_policy: RetryPolicy::default(),
+            retry_count: 0,
+            statistics_burst_read: BurstRead::Detect,
         }
     }
     /// Creates a new instance of the AP33772S device and checks if the device is present on the bus.
     /// TODO: Integrate Setting of Thermal Resistance and Thresholds matching RotoPD Board. This also handles the timings required for initialisation by using the provided hals delay method
     #[maybe_async::maybe_async]
-    pub async fn new_default(i2c: I2C, delay: D, interrupt_pin: P) -> Result<Self, Ap33772sError> {
+    pub async fn new_default(
+        i2c: I2C,
+        delay: D,
+        interrupt_pin: P,
+    ) -> Result<Self, Ap33772sError<I2C::Error>> {
         let mut device = Self::new(i2c, delay, interrupt_pin);
         device.is_device_present().await?;
         // TODO: Initialize Thermal Resistances and Thresholds
@@ -282,20 +678,191 @@ impl<I2C: I2c, D: DelayNs, P: InputPin> Ap33772s<I2C, D, P> {
 #[cfg(not(feature = "interrupts"))]
 impl<I2C: I2c, D: DelayNs> Ap33772s<I2C, D> {
     pub const ADDRESS: SevenBitAddress = 0x52;
+
+    /// Enables or disables write verification for the configuration setters.
+    ///
+    /// When enabled, [`Self::set_thresholds`], [`Self::set_thermal_resistances`],
+    /// [`Self::set_minimum_selection_voltage`] and [`Self::set_power_delivery_mode`] read every
+    /// register back after writing it and return [`Ap33772sError::WriteVerificationFailed`] if the
+    /// device holds a different value. This doubles the number of I2C transactions for those setters.
+    ///
+    /// Write verification is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::{Ap33772s, types::Thresholds};
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// device.set_write_verification(true);
+    /// device.set_thresholds(Thresholds::default())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError::WriteVerificationFailed`]: crate::errors::Ap33772sError::WriteVerificationFailed
+    pub fn set_write_verification(&mut self, enabled: bool) {
+        self.write_verification = enabled;
+    }
+
+    /// Returns `true` if write verification is enabled. See [`Self::set_write_verification`].
+    pub fn write_verification(&self) -> bool {
+        self.write_verification
+    }
+
+    /// Enables or disables tracking of the over voltage and over current thresholds.
+    ///
+    /// With a [`ThresholdTracking`] policy set, [`Self::negotiate_power_delivery`] and
+    /// [`Self::negotiate_maximum_power_delivery`] recalculate OVPTHR and OCPTHR for every contract,
+    /// overwriting the values written by [`Self::set_thresholds`]. The under voltage and temperature
+    /// thresholds are not affected.
+    ///
+    /// The recalculated thresholds are checked against the [`SafetyLimits`] like those given to
+    /// [`Self::set_thresholds`]. A request whose thresholds would exceed a limit is refused before
+    /// it is sent.
+    ///
+    /// Threshold tracking is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::{Ap33772s, types::{Rounding, ThresholdTracking}, units::*};
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) {
+    /// device.set_threshold_tracking(Some(ThresholdTracking {
+    ///     over_voltage_margin_percentage: 5.0,
+    ///     minimum_over_voltage_margin: ElectricPotential::new::<millivolt>(400.0),
+    ///     rounding: Rounding::Up,
+    /// }));
+    /// # }
+    /// ```
+    pub fn set_threshold_tracking(&mut self, tracking: Option<ThresholdTracking>) {
+        self.threshold_tracking = tracking;
+    }
+
+    /// Returns the threshold tracking policy, if enabled. See [`Self::set_threshold_tracking`].
+    pub fn threshold_tracking(&self) -> Option<ThresholdTracking> {
+        self.threshold_tracking
+    }
+
+    /// Sets the current rating of the USB-C cable. See [`CableRating`].
+    ///
+    /// With a rating set, [`Self::negotiate_power_delivery`] and
+    /// [`Self::negotiate_maximum_power_delivery`] lower every current request above the rating to
+    /// the rating, and [`Self::set_thresholds`] and [`ThresholdTracking`] cap OCPTHR to it. An
+    /// OCPTHR of zero, which selects 110% of the PDO maximum current, is replaced by the rating.
+    ///
+    /// No cable rating is set by default.
+    ///
+    /// **Note**: The OCP trips at 110% of OCPTHR, so a 3A cable is protected at 3.3A.
+    pub fn set_cable_rating(&mut self, rating: Option<CableRating>) {
+        self.cable_rating = rating;
+    }
+
+    /// Returns the cable rating, if set. See [`Self::set_cable_rating`].
+    pub fn cable_rating(&self) -> Option<CableRating> {
+        self.cable_rating
+    }
+
+    /// Sets the [`SafetyLimits`] the driver refuses to exceed. Requests and thresholds above a
+    /// limit return [`Ap33772sError::SafetyLimitExceeded`] before anything is written.
+    ///
+    /// The limits can only be set while creating the driver and cannot be loosened: calling this
+    /// again keeps the stricter of each limit. Thresholds written before the limits are set are
+    /// not checked, so use [`Self::new_default_with_limits`] rather than calling this on the
+    /// driver returned by [`Self::new_default`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::{Ap33772s, types::SafetyLimits, units::*};
+    /// # fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut device = Ap33772s::new(i2c, delay).with_safety_limits(SafetyLimits {
+    ///     maximum_voltage: ElectricPotential::new::<volt>(12.0),
+    ///     ..SafetyLimits::default()
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError::SafetyLimitExceeded`]: crate::errors::Ap33772sError::SafetyLimitExceeded
+    pub fn with_safety_limits(mut self, limits: SafetyLimits) -> Self {
+        self.safety_limits = Some(match self.safety_limits {
+            Some(existing) => existing.tightened(&limits),
+            None => limits,
+        });
+        self
+    }
+
+    /// Returns the safety limits, if set. See [`Self::with_safety_limits`].
+    pub fn safety_limits(&self) -> Option<SafetyLimits> {
+        self.safety_limits
+    }
+
+    /// Sets how failed I2C transfers are retried. See [`RetryPolicy`].
+    ///
+    /// Retries are disabled by default.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Returns the retry policy. See [`Self::set_retry_policy`].
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Returns the number of I2C transfers that have been retried since the driver was created or
+    /// the count was last reset. A rising count points to a noisy bus or a busy device.
+    pub fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
+    /// Resets the count returned by [`Self::retry_count`] to zero.
+    pub fn reset_retry_count(&mut self) {
+        self.retry_count = 0;
+    }
+
+    /// Sets whether [`Self::get_statistics`] reads all statistics in one transfer. See [`BurstRead`].
+    ///
+    /// Setting [`BurstRead::Detect`] repeats the detection on the next read.
+    pub fn set_statistics_burst_read(&mut self, burst_read: BurstRead) {
+        self.statistics_burst_read = burst_read;
+    }
+
+    /// Returns how statistics are read. Once detection has run this is either
+    /// [`BurstRead::Enabled`] or [`BurstRead::Disabled`].
+    pub fn statistics_burst_read(&self) -> BurstRead {
+        self.statistics_burst_read
+    }
     /// Checks if the device is present on the I2C bus. It checks an command register of the device and matches with the expected value.
     #[maybe_async::maybe_async]
-    pub async fn is_device_present(&mut self) -> Result<(), Ap33772sError> {
-        let system_control = self.read_one_byte_command::<SystemControl>().await?;
-        system_control
-            .command_version()
-            .map_err(|_| Ap33772sError::WrongCommandVersion(system_control.raw_value()))?;
+    pub async fn is_device_present(&mut self) -> Result<(), Ap33772sError<I2C::Error>> {
+        self.get_device_info().await?;
         Ok(())
     }
 
+    /// Reads the command version from the SYSTEM register and returns it with the I2C address
+    /// as a [`DeviceInfo`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Ap33772sError::WrongCommandVersion`] with the raw SYSTEM register value if the
+    /// command version is not one this driver supports, so units with an unknown firmware
+    /// revision are refused rather than driven with a command set that may not match.
+    #[maybe_async::maybe_async]
+    pub async fn get_device_info(&mut self) -> Result<DeviceInfo, Ap33772sError<I2C::Error>> {
+        let system_control = self.read_one_byte_command::<SystemControl>().await?;
+        let command_version = system_control.command_version().map_err(|_| {
+            Ap33772sError::<I2C::Error>::WrongCommandVersion(system_control.raw_value())
+        })?;
+        Ok(DeviceInfo {
+            command_version,
+            address: Self::ADDRESS,
+        })
+    }
+
     /// Performs a hard reset on the device. This will completely reset the device and put it in a fresh state
     /// and require the I2C connection to be severed on the RotoPD board.
     #[maybe_async::maybe_async]
-    pub async fn hard_reset(&mut self) -> Result<(), Ap33772sError> {
+    pub async fn hard_reset(&mut self) -> Result<(), Ap33772sError<I2C::Error>> {
         let power_delivery_command_message = PowerDeliveryCommandMessage::builder()
             .with_HardResetEnable(true)
             .build();
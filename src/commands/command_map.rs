@@ -171,3 +171,15 @@ pub enum Command {
     /// Datasheet Name: GPIO. not found in datasheet but found in Raspberry Pi I2C User Guide
     Gpio = 0x52,
 }
+
+impl Command {
+    /// Returns `false` for the message registers, where every write sends a message to the source.
+    /// Writing one of these twice sends a second power request or hard reset, so failed writes to
+    /// them are never retried.
+    pub const fn is_idempotent(self) -> bool {
+        !matches!(
+            self,
+            Command::PowerDeliveryRequestMessage | Command::PowerDeliveryCommandMessage
+        )
+    }
+}
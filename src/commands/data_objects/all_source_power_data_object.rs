@@ -74,7 +74,7 @@ impl AllSourceDataPowerDataObject {
     pub fn get_power_data_object(&self, index: PowerDataObject) -> &SourcePowerRangeDataObject {
         // These assertions should never fire, but we include them for safety. The PowerDataObjects are always in the range 1-13
         assert!(
-            usize::from(index) < self.power_data_objects.len(),
+            usize::from(index) <= self.power_data_objects.len(),
             "Index out of bounds for power data objects"
         );
         assert_ne!(
@@ -85,4 +85,13 @@ impl AllSourceDataPowerDataObject {
         let index = usize::from(index) - 1;
         &self.power_data_objects[index]
     }
+
+    /// Returns `true` if the source has sent its EPR capabilities, which only happens once the
+    /// AP33772S has entered EPR Mode. PDOs 8 to 13 can only be requested after this.
+    pub fn extended_power_range_entered(&self) -> bool {
+        self.power_data_objects.iter().any(|data_object| {
+            matches!(data_object, SourcePowerRangeDataObject::Extended(_))
+                && data_object.is_detected()
+        })
+    }
 }
@@ -5,7 +5,7 @@ use crate::commands::data_objects::extended_power_range_data_object::MinimumVolt
 use crate::commands::data_objects::standard_power_range_data_object::MinimumVoltage as StandardMinimumVoltage;
 use crate::errors::{Ap33772sError, RequestError};
 use crate::types::command_structures::{
-    ExtendedPowerRangeDataObject, StandardPowerRangeDataObject,
+    Command, ExtendedPowerRangeDataObject, StandardPowerRangeDataObject,
 };
 use crate::units::*;
 
@@ -29,6 +29,19 @@ impl SourcePowerRangeDataObject {
             }
         }
     }
+    /// Returns the highest voltage the AP33772S supports for this type of PDO, 21V for Standard
+    /// and 28V for Extended Power Range
+    pub fn absolute_maximum_voltage(&self) -> ElectricPotential {
+        let millivolts = match self {
+            SourcePowerRangeDataObject::Standard(_) => {
+                StandardPowerRangeDataObject::ABSOLUTE_MAXIMUM_VOLTAGE
+            }
+            SourcePowerRangeDataObject::Extended(_) => {
+                ExtendedPowerRangeDataObject::ABSOLUTE_MAXIMUM_VOLTAGE
+            }
+        };
+        ElectricPotential::new::<millivolt>(f32::from(millivolts))
+    }
     pub fn source_power_type(&self) -> PowerType {
         match self {
             SourcePowerRangeDataObject::Standard(data_object) => data_object.source_power_type(),
@@ -67,7 +80,10 @@ impl SourcePowerRangeDataObject {
                                     StandardPowerRangeDataObject::VOLTAGE_RESOLUTION as f32,
                                 ))
                         }
-                        _ => Err(Ap33772sError::ConversionFailed),
+                        _ => Err(Ap33772sError::conversion(
+                            Command::AllSourcesPowerDataObject,
+                            f32::from(data_object.minimum_voltage_or_peak_current().value()),
+                        )),
                     },
                     None => Err(Ap33772sError::InvalidRequest(RequestError::MissingArgument)),
                 }
@@ -84,7 +100,10 @@ impl SourcePowerRangeDataObject {
                                     ExtendedPowerRangeDataObject::VOLTAGE_RESOLUTION as f32,
                                 ))
                         }
-                        _ => Err(Ap33772sError::ConversionFailed),
+                        _ => Err(Ap33772sError::conversion(
+                            Command::AllSourcesPowerDataObject,
+                            f32::from(data_object.minimum_voltage_or_peak_current().value()),
+                        )),
                     },
                     None => Err(Ap33772sError::InvalidRequest(RequestError::MissingArgument)),
                 }
@@ -1,3 +1,4 @@
+use crate::commands::command_map::Command;
 use crate::commands::data_objects::source_power_range_data_object::{
     PeakCurrent, SourceMaximumCurrent,
 };
@@ -50,7 +51,10 @@ impl StandardPowerRangeDataObject {
     pub fn max_voltage(&self) -> Result<ElectricPotential, Ap33772sError> {
         let scaled_voltage = u16::from(self.raw_max_voltage())
             .checked_mul(Self::VOLTAGE_RESOLUTION)
-            .ok_or(Ap33772sError::ConversionFailed)?;
+            .ok_or(Ap33772sError::conversion(
+                Command::AllSourcesPowerDataObject,
+                f32::from(self.raw_max_voltage()),
+            ))?;
         Ok(ElectricPotential::new::<millivolt>(f32::from(
             scaled_voltage,
         )))
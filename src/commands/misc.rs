@@ -1 +1 @@
-mod gpio;
+pub mod gpio;
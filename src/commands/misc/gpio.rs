@@ -4,14 +4,18 @@ use crate::{
     commands::command_map::Command, impl_one_byte_read_command, impl_one_byte_write_command,
 };
 
-/// TODO: Find out more about this mystery struct
+/// The GPIO register. The Raspberry Pi I2C user guide lists it at 0x52 as a one byte RO/RW
+/// register with a default of 00h, but no document describes its bits, so direction and level
+/// control cannot be decoded. The value is exposed as a whole byte.
 ///
 /// Datasheet Name: GPIO
 #[bitfield(u8, default = 0x00)]
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Gpio {
+    /// The undocumented register contents
     #[bits(0..=7, rw)]
-    pub unknown: u8,
+    pub value: u8,
 }
 
 impl_one_byte_read_command!(Gpio, Command::Gpio);
@@ -1,18 +1,26 @@
 use crate::commands::command_map::Command;
+use crate::commands::data_objects::source_power_range_data_object::SourcePowerRangeDataObject;
 use crate::errors::Ap33772sError;
 use crate::impl_two_byte_write_command;
+use crate::types::Rounding;
 use crate::units::*;
 use bitbybit::{bitenum, bitfield};
+/// The request message sent to the source, selecting a PDO with the operating current and, for
+/// a PPS or AVS PDO, the output voltage.
+///
+/// Datasheet Name: PD_REQMSG
 #[bitfield(u16, default = 0x0000)]
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PowerDeliveryRequestMessage {
-    #[bits(0..=7, w)]
-    pub voltage_selection: u8, // Handle the different units depending on the power mode
-    #[bits(8..=11, w)]
+    /// The output voltage in 100mV units for a PPS APDO or 200mV units for an AVS APDO, unused
+    /// for a Fixed PDO
+    #[bits(0..=7, rw)]
+    pub voltage_selection: u8,
+    #[bits(8..=11, rw)]
     pub current_selection: OperatingCurrentSelection,
-    #[bits(12..=15, w)]
-    pub power_data_object_index: PowerDataObject,
+    #[bits(12..=15, rw)]
+    pub power_data_object_index: Option<PowerDataObject>,
 }
 
 /// The Power Data Onject (PDO) represents the different power modes that are supported in
@@ -54,6 +62,27 @@ impl From<PowerDataObject> for usize {
     }
 }
 
+impl From<PowerDataObject> for Command {
+    /// Returns the SRC_SPR_PDOx or SRC_EPR_PDOx register holding this Power Data Object
+    fn from(value: PowerDataObject) -> Self {
+        match value {
+            PowerDataObject::StandardPowerRange1 => Command::SourceStandardPowerRange1,
+            PowerDataObject::StandardPowerRange2 => Command::SourceStandardPowerRange2,
+            PowerDataObject::StandardPowerRange3 => Command::SourceStandardPowerRange3,
+            PowerDataObject::StandardPowerRange4 => Command::SourceStandardPowerRange4,
+            PowerDataObject::StandardPowerRange5 => Command::SourceStandardPowerRange5,
+            PowerDataObject::StandardPowerRange6 => Command::SourceStandardPowerRange6,
+            PowerDataObject::StandardPowerRange7 => Command::SourceStandardPowerRange7,
+            PowerDataObject::ExtendedPowerRange8 => Command::SourceExtendedPowerRange8,
+            PowerDataObject::ExtendedPowerRange9 => Command::SourceExtendedPowerRange9,
+            PowerDataObject::ExtendedPowerRange10 => Command::SourceExtendedPowerRange10,
+            PowerDataObject::ExtendedPowerRange11 => Command::SourceExtendedPowerRange11,
+            PowerDataObject::ExtendedPowerRange12 => Command::SourceExtendedPowerRange12,
+            PowerDataObject::ExtendedPowerRange13 => Command::SourceExtendedPowerRange13,
+        }
+    }
+}
+
 impl TryFrom<usize> for PowerDataObject {
     type Error = Ap33772sError;
 
@@ -74,7 +103,10 @@ impl TryFrom<usize> for PowerDataObject {
             10 => Ok(PowerDataObject::ExtendedPowerRange11),
             11 => Ok(PowerDataObject::ExtendedPowerRange12),
             12 => Ok(PowerDataObject::ExtendedPowerRange13),
-            _ => Err(Ap33772sError::ConversionFailed),
+            _ => Err(Ap33772sError::conversion(
+                Command::PowerDeliveryRequestMessage,
+                value as f32,
+            )),
         }
     }
 }
@@ -126,6 +158,11 @@ pub enum OperatingCurrentSelection {
     Maximum = 15,
 }
 impl OperatingCurrentSelection {
+    const FIRST_STEP_CURRENT: f32 = 1000.0; // mA
+    const LAST_STEP_CURRENT: f32 = 4500.0; // mA
+    const LAST_STEP_INDEX: f32 = 14.0;
+    const CURRENT_STEP: f32 = 250.0; // mA
+
     /// Returns the Operating Current in a useable `uom` ElectricCurrent. Where 5A is the "Maximum" value
     pub fn current(&self) -> ElectricCurrent {
         use OperatingCurrentSelection::*;
@@ -149,6 +186,78 @@ impl OperatingCurrentSelection {
         };
         ElectricCurrent::new::<milliampere>(current)
     }
+
+    /// Converts a current to the operating current selection using the given [`Rounding`] policy.
+    ///
+    /// The selections are 250mA apart from 1A to 4.5A, followed by the 5A
+    /// [`OperatingCurrentSelection::Maximum`]. A current below 1A is only accepted with
+    /// [`Rounding::Up`], and a current above 5A with [`Rounding::Down`]. [`Rounding::Nearest`]
+    /// accepts currents within half a step of the range. Other currents return a conversion error.
+    ///
+    /// ```rust
+    /// use ap33772s_rs::types::Rounding;
+    /// use ap33772s_rs::types::command_structures::OperatingCurrentSelection;
+    /// use ap33772s_rs::units::*;
+    ///
+    /// let current = ElectricCurrent::new::<milliampere>(2100.0);
+    /// assert_eq!(
+    ///     OperatingCurrentSelection::from_current(current, Rounding::Down),
+    ///     Ok(OperatingCurrentSelection::_2A)
+    /// );
+    /// assert_eq!(
+    ///     OperatingCurrentSelection::from_current(current, Rounding::Up),
+    ///     Ok(OperatingCurrentSelection::_2_25A)
+    /// );
+    /// ```
+    pub fn from_current(
+        current: ElectricCurrent,
+        rounding: Rounding,
+    ) -> Result<Self, Ap33772sError> {
+        let milliamperes = current.get::<milliampere>();
+        let conversion_failed =
+            || Ap33772sError::conversion(Command::PowerDeliveryRequestMessage, milliamperes);
+        if !milliamperes.is_finite() {
+            return Err(conversion_failed());
+        }
+        // The position between selections, the step to Maximum is 500mA instead of 250mA
+        let position = if milliamperes <= Self::LAST_STEP_CURRENT {
+            (milliamperes - Self::FIRST_STEP_CURRENT) / Self::CURRENT_STEP
+        } else {
+            Self::LAST_STEP_INDEX
+                + (milliamperes - Self::LAST_STEP_CURRENT) / (2.0 * Self::CURRENT_STEP)
+        };
+        let maximum_index = f32::from(Self::Maximum as u8);
+        // Rounding up never selects below the request and rounding down never above it, so they
+        // can clamp to the lowest or highest selection
+        let index = match rounding {
+            Rounding::Down => rounding.round(position).min(maximum_index),
+            Rounding::Nearest => rounding.round(position),
+            Rounding::Up => rounding.round(position).max(0.0),
+        };
+        if !(0.0..=maximum_index).contains(&index) {
+            return Err(conversion_failed());
+        }
+        Self::try_from(index as usize)
+    }
+
+    /// Returns the highest selection that does not exceed the maximum current advertised by the
+    /// PDO, see [`SourceMaximumCurrent::max_range`].
+    ///
+    /// Returns `None` when the PDO advertises less than 1.24A, as the source may then allow less
+    /// than 1A, the lowest selection. This includes PDOs that were not detected.
+    ///
+    /// [`SourceMaximumCurrent::max_range`]: crate::types::command_structures::SourceMaximumCurrent::max_range
+    pub fn highest_allowed_for(data_object: &SourcePowerRangeDataObject) -> Option<Self> {
+        let maximum_current = data_object.get_max_current();
+        if maximum_current.min_range() < Self::_1A.current() {
+            return None;
+        }
+        let maximum = maximum_current.max_range();
+        (0..=Self::Maximum as usize)
+            .rev()
+            .filter_map(|index| Self::try_from(index).ok())
+            .find(|selection| selection.current() <= maximum)
+    }
 }
 
 impl TryFrom<usize> for OperatingCurrentSelection {
@@ -174,7 +283,10 @@ impl TryFrom<usize> for OperatingCurrentSelection {
             13 => Ok(OperatingCurrentSelection::_4_25A),
             14 => Ok(OperatingCurrentSelection::_4_5A),
             15 => Ok(OperatingCurrentSelection::Maximum),
-            _ => Err(Ap33772sError::ConversionFailed),
+            _ => Err(Ap33772sError::conversion(
+                Command::PowerDeliveryRequestMessage,
+                value as f32,
+            )),
         }
     }
 }
@@ -47,7 +47,9 @@ impl CurrentRequested {
         let scaled_current = self
             .raw_current()
             .checked_mul(Self::CURRENT_RESOLUTION)
-            .ok_or(Ap33772sError::ConversionFailed)?;
+            .ok_or_else(|| {
+                Ap33772sError::conversion(Command::CurrentRequested, f32::from(self.raw_current()))
+            })?;
         Ok(ElectricCurrent::new::<milliampere>(f32::from(
             scaled_current,
         )))
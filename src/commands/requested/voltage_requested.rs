@@ -46,7 +46,9 @@ impl VoltageRequested {
         let scaled_voltage = self
             .raw_voltage()
             .checked_mul(Self::VOLTAGE_RESOLUTION)
-            .ok_or(Ap33772sError::ConversionFailed)?;
+            .ok_or_else(|| {
+                Ap33772sError::conversion(Command::VoltageRequested, f32::from(self.raw_voltage()))
+            })?;
         Ok(ElectricPotential::new::<millivolt>(f32::from(
             scaled_voltage,
         )))
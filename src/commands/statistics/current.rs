@@ -36,7 +36,9 @@ impl Current {
     pub fn current(&self) -> Result<ElectricCurrent, Ap33772sError> {
         u16::from(self.raw_current())
             .checked_mul(Self::CURRENT_RESOLUTION)
-            .ok_or(Ap33772sError::ConversionFailed)
+            .ok_or_else(|| {
+                Ap33772sError::conversion(Command::Current, f32::from(self.raw_current()))
+            })
             .map(|scaled_current| ElectricCurrent::new::<milliampere>(f32::from(scaled_current)))
     }
 }
@@ -1,5 +1,6 @@
 use crate::commands::command_map::Command;
 use crate::errors::Ap33772sError;
+use crate::types::Rounding;
 use crate::units::*;
 use crate::{impl_one_byte_read_command, impl_one_byte_write_command};
 use bitbybit::bitfield;
@@ -33,13 +34,25 @@ impl MinimumSelectionVoltage {
         Self::convert_raw_voltage_to_voltage(self.raw_voltage())
     }
     pub fn convert_voltage_to_raw_voltage(voltage: ElectricPotential) -> Result<u8, Ap33772sError> {
+        Self::convert_voltage_to_raw_voltage_rounded(voltage, Rounding::Down)
+    }
+    /// Converts a voltage to the raw register value, rounding to the 200mV resolution using the
+    /// given [`Rounding`] policy.
+    pub fn convert_voltage_to_raw_voltage_rounded(
+        voltage: ElectricPotential,
+        rounding: Rounding,
+    ) -> Result<u8, Ap33772sError> {
+        let conversion_failed = || {
+            Ap33772sError::conversion(Command::MinimumSelectionVoltage, voltage.get::<millivolt>())
+        };
         if !voltage.is_finite() || voltage.is_sign_negative() {
-            return Err(Ap33772sError::ConversionFailed);
+            return Err(conversion_failed());
         }
-        let raw_value = voltage.get::<millivolt>() / f32::from(Self::SELECTION_VOLTAGE_RESOLUTION);
+        let raw_value = rounding
+            .round(voltage.get::<millivolt>() / f32::from(Self::SELECTION_VOLTAGE_RESOLUTION));
 
         if raw_value > u8::MAX as f32 {
-            return Err(Ap33772sError::ConversionFailed);
+            return Err(conversion_failed());
         }
 
         Ok(raw_value as u8)
@@ -50,7 +63,9 @@ impl MinimumSelectionVoltage {
         u16::from(raw_voltage)
             .checked_mul(Self::SELECTION_VOLTAGE_RESOLUTION)
             .map(|scaled| ElectricPotential::new::<millivolt>(f32::from(scaled)))
-            .ok_or(Ap33772sError::ConversionFailed)
+            .ok_or_else(|| {
+                Ap33772sError::conversion(Command::MinimumSelectionVoltage, f32::from(raw_voltage))
+            })
     }
 }
 
@@ -36,7 +36,9 @@ impl Voltage {
         self.raw_voltage()
             .checked_mul(Self::VOLTAGE_RESOLUTION)
             .map(|scaled_voltage| ElectricPotential::new::<millivolt>(f32::from(scaled_voltage)))
-            .ok_or(Ap33772sError::ConversionFailed)
+            .ok_or_else(|| {
+                Ap33772sError::conversion(Command::Voltage, f32::from(self.raw_voltage()))
+            })
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::commands::command_map::Command;
 use crate::errors::Ap33772sError;
 use crate::units::*;
 pub mod thermal_resistance_100;
@@ -10,17 +11,15 @@ pub mod thermal_resistance_75;
 // chosen for simplicity and safety, as it covers the expected input range and
 // failure modes. If more granular error handling is needed in the future,
 // consider distinguishing between different failure cases.
+// The `command` identifies which of the four thermal resistance registers the value is for.
 pub fn convert_resistance_to_raw_resistance(
     resistance: ElectricalResistance,
+    command: Command,
 ) -> Result<u16, Ap33772sError> {
-    if !resistance.is_finite() || !resistance.is_sign_positive() {
-        return Err(Ap33772sError::ConversionFailed);
-    }
-
     let raw_value = resistance.get::<ohm>();
 
-    if raw_value > u16::MAX as f32 {
-        return Err(Ap33772sError::ConversionFailed);
+    if !resistance.is_finite() || !resistance.is_sign_positive() || raw_value > u16::MAX as f32 {
+        return Err(Ap33772sError::conversion(command, raw_value));
     }
 
     Ok(raw_value as u16)
@@ -1,5 +1,6 @@
 use crate::commands::command_map::Command;
 use crate::errors::Ap33772sError;
+use crate::types::Rounding;
 use crate::units::*;
 use crate::{impl_one_byte_read_command, impl_one_byte_write_command};
 use bitbybit::bitfield;
@@ -27,13 +28,28 @@ impl DeRatingThreshold {
     pub fn convert_temperature_to_raw_temperature(
         temperature: ThermodynamicTemperature,
     ) -> Result<u8, Ap33772sError> {
-        if !temperature.is_finite() || !temperature.is_sign_positive() {
-            return Err(Ap33772sError::ConversionFailed);
+        Self::convert_temperature_to_raw_temperature_rounded(temperature, Rounding::Down)
+    }
+    /// Converts a temperature in degrees Celsius to the raw temperature value, rounding to whole
+    /// degrees using the given [`Rounding`] policy.
+    pub fn convert_temperature_to_raw_temperature_rounded(
+        temperature: ThermodynamicTemperature,
+        rounding: Rounding,
+    ) -> Result<u8, Ap33772sError> {
+        let celsius = temperature.get::<degree_celsius>();
+        if !celsius.is_finite() || celsius < 0.0 {
+            return Err(Ap33772sError::conversion(
+                Command::DeRatingThreshold,
+                celsius,
+            ));
         }
-        let raw_value = temperature.get::<degree_celsius>() as u16;
+        let raw_value = rounding.round(celsius);
 
-        if raw_value > u8::MAX as u16 {
-            return Err(Ap33772sError::ConversionFailed);
+        if raw_value > f32::from(u8::MAX) {
+            return Err(Ap33772sError::conversion(
+                Command::DeRatingThreshold,
+                celsius,
+            ));
         }
 
         Ok(raw_value as u8)
@@ -1,5 +1,6 @@
 use crate::commands::command_map::Command;
 use crate::errors::Ap33772sError;
+use crate::types::Rounding;
 use crate::units::*;
 use crate::{impl_one_byte_read_command, impl_one_byte_write_command};
 use bitbybit::bitfield;
@@ -39,17 +40,37 @@ impl OverCurrentProtectionThreshold {
     pub fn current(&self) -> Result<ElectricCurrent, Ap33772sError> {
         u16::from(self.raw_current())
             .checked_mul(Self::CURRENT_RESOLUTION)
-            .ok_or(Ap33772sError::ConversionFailed)
+            .ok_or_else(|| {
+                Ap33772sError::conversion(
+                    Command::OverCurrentProtectionThreshold,
+                    f32::from(self.raw_current()),
+                )
+            })
             .map(|scaled_current| ElectricCurrent::new::<milliampere>(f32::from(scaled_current)))
     }
     pub fn convert_current_to_raw_current(current: ElectricCurrent) -> Result<u8, Ap33772sError> {
+        Self::convert_current_to_raw_current_rounded(current, Rounding::Down)
+    }
+    /// Converts a current to the raw register value, rounding to the 50mA resolution using the
+    /// given [`Rounding`] policy.
+    pub fn convert_current_to_raw_current_rounded(
+        current: ElectricCurrent,
+        rounding: Rounding,
+    ) -> Result<u8, Ap33772sError> {
         if !current.is_finite() || !current.is_sign_positive() {
-            return Err(Ap33772sError::ConversionFailed);
+            return Err(Ap33772sError::conversion(
+                Command::OverCurrentProtectionThreshold,
+                current.get::<milliampere>(),
+            ));
         }
-        let raw_value = current.get::<milliampere>() / Self::CURRENT_RESOLUTION as f32;
+        let raw_value =
+            rounding.round(current.get::<milliampere>() / Self::CURRENT_RESOLUTION as f32);
 
         if raw_value > f32::from(u8::MAX) {
-            return Err(Ap33772sError::ConversionFailed);
+            return Err(Ap33772sError::conversion(
+                Command::OverCurrentProtectionThreshold,
+                current.get::<milliampere>(),
+            ));
         }
 
         Ok(raw_value as u8)
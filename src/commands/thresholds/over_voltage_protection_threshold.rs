@@ -1,5 +1,6 @@
 use crate::commands::command_map::Command;
 use crate::errors::Ap33772sError;
+use crate::types::Rounding;
 use crate::units::*;
 use crate::{impl_one_byte_read_command, impl_one_byte_write_command};
 use bitbybit::bitfield;
@@ -37,17 +38,37 @@ impl OverVoltageProtectionThreshold {
     pub fn voltage(&self) -> Result<ElectricPotential, Ap33772sError> {
         u16::from(self.raw_voltage())
             .checked_mul(Self::VOLTAGE_RESOLUTION)
-            .ok_or(Ap33772sError::ConversionFailed)
+            .ok_or_else(|| {
+                Ap33772sError::conversion(
+                    Command::OverVoltageProtectionThreshold,
+                    f32::from(self.raw_voltage()),
+                )
+            })
             .map(|scaled_voltage| ElectricPotential::new::<millivolt>(f32::from(scaled_voltage)))
     }
     pub fn convert_voltage_to_raw_voltage(voltage: ElectricPotential) -> Result<u8, Ap33772sError> {
+        Self::convert_voltage_to_raw_voltage_rounded(voltage, Rounding::Down)
+    }
+    /// Converts a voltage offset to the raw register value, rounding to the 80mV resolution using
+    /// the given [`Rounding`] policy.
+    pub fn convert_voltage_to_raw_voltage_rounded(
+        voltage: ElectricPotential,
+        rounding: Rounding,
+    ) -> Result<u8, Ap33772sError> {
         if !voltage.is_finite() || !voltage.is_sign_positive() {
-            return Err(Ap33772sError::ConversionFailed);
+            return Err(Ap33772sError::conversion(
+                Command::OverVoltageProtectionThreshold,
+                voltage.get::<millivolt>(),
+            ));
         }
-        let raw_value = voltage.get::<millivolt>() / f32::from(Self::VOLTAGE_RESOLUTION);
+        let raw_value =
+            rounding.round(voltage.get::<millivolt>() / f32::from(Self::VOLTAGE_RESOLUTION));
 
         if raw_value > f32::from(u8::MAX) {
-            return Err(Ap33772sError::ConversionFailed);
+            return Err(Ap33772sError::conversion(
+                Command::OverVoltageProtectionThreshold,
+                voltage.get::<millivolt>(),
+            ));
         }
 
         Ok(raw_value as u8)
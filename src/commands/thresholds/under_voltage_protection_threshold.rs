@@ -34,6 +34,17 @@ pub enum UnderVoltageThreshold {
     // Other values are reserved and should not be used.
 }
 
+impl UnderVoltageThreshold {
+    /// Returns the threshold as a percentage of the requested voltage
+    pub const fn percentage(self) -> u8 {
+        match self {
+            UnderVoltageThreshold::EightyPercent => 80,
+            UnderVoltageThreshold::SeventyFivePercent => 75,
+            UnderVoltageThreshold::SeventyPercent => 70,
+        }
+    }
+}
+
 impl_one_byte_read_command!(
     UnderVoltageProtectionThreshold,
     Command::UnderVoltageProtectionThreshold
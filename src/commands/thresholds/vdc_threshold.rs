@@ -1,8 +1,18 @@
-use crate::{
-    commands::command_map::Command, impl_one_byte_read_command, impl_one_byte_write_command,
-};
+use crate::commands::command_map::Command;
+use crate::errors::Ap33772sError;
+use crate::types::Rounding;
+use crate::units::*;
+use crate::{impl_one_byte_read_command, impl_one_byte_write_command};
 use bitbybit::bitfield;
 
+/// The VDCTHR register holds the allowed difference, in percent, between the requested voltage
+/// (VREQ) and the measured output voltage (VOLTAGE).
+///
+/// This register is not in the datasheet or the register summary of the Raspberry Pi I2C user
+/// guide. It comes from the vendor Arduino examples, and the EVB user guide revision history lists
+/// "Remove VDC" for firmware 1.1, so writes may have no effect on current parts.
+///
+/// Datasheet Name: VDCTHR
 #[bitfield(u8, default = 0x00)]
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -12,8 +22,24 @@ pub struct VDCTHR {
     pub percentage: u8,
 }
 
-// TODO: Solve this mystery register
-// Doesnt show up in datasheet but is in the arduino vendor support examples
+impl VDCTHR {
+    /// Returns the threshold as a [`Ratio`] with a resolution of 1%.
+    pub fn threshold(&self) -> Ratio {
+        Ratio::new::<percent>(f32::from(self.percentage()))
+    }
+    /// Converts a ratio to the raw percentage value, rounding down to whole percent.
+    pub fn convert_ratio_to_raw_percentage(threshold: Ratio) -> Result<u8, Ap33772sError> {
+        let percentage = threshold.get::<percent>();
+        if !percentage.is_finite() || percentage < 0.0 {
+            return Err(Ap33772sError::conversion(Command::Vdcthr, percentage));
+        }
+        let raw_value = Rounding::Down.round(percentage);
+        if raw_value > f32::from(u8::MAX) {
+            return Err(Ap33772sError::conversion(Command::Vdcthr, percentage));
+        }
+        Ok(raw_value as u8)
+    }
+}
 
 impl_one_byte_read_command!(VDCTHR, Command::Vdcthr);
 impl_one_byte_write_command!(VDCTHR, Command::Vdcthr);
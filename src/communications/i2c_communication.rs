@@ -5,8 +5,10 @@ use super::traits::{
     ReadOneByteCommand, ReadTwoByteCommand, WriteOneByteCommand, WriteTwoByteCommand,
 };
 use crate::ap33772s::Ap33772s;
-use crate::errors::Ap33772sError;
+use crate::commands::command_map::Command;
+use crate::errors::{Ap33772sError, Operation};
 use crate::hal::DelayNs;
+use crate::hal::Error;
 use crate::hal::I2c;
 
 impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
@@ -14,41 +16,34 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     pub async fn write_one_byte_command(
         &mut self,
         command: impl WriteOneByteCommand,
-    ) -> Result<(), Ap33772sError> {
-        let command_address = command.get_command() as u8;
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let register = command.get_command();
         let data = command.raw_value();
-        self.i2c
-            .write(Self::ADDRESS, &[command_address, data])
-            .await?;
-        Ok(())
+        self.bus_write(register, &[register as u8, data]).await
     }
     #[maybe_async::maybe_async]
     pub async fn read_one_byte_command<CommandRegister>(
         &mut self,
-    ) -> Result<CommandRegister, Ap33772sError>
+    ) -> Result<CommandRegister, Ap33772sError<I2C::Error>>
     where
         CommandRegister: ReadOneByteCommand,
     {
         let mut data: [u8; 1] = [0x00];
-        let command_address = CommandRegister::command() as u8;
-        self.i2c
-            .write_read(Self::ADDRESS, &[command_address], &mut data)
-            .await?;
+        let register = CommandRegister::command();
+        self.bus_write_read(register, &mut data).await?;
         Ok(CommandRegister::new_with_raw_value(data[0]))
     }
 
     #[maybe_async::maybe_async]
     pub(crate) async fn read_two_byte_command<CommandRegister>(
         &mut self,
-    ) -> Result<CommandRegister, Ap33772sError>
+    ) -> Result<CommandRegister, Ap33772sError<I2C::Error>>
     where
         CommandRegister: ReadTwoByteCommand,
     {
         let mut data: [u8; 2] = [0x00; 2];
-        let command_address = CommandRegister::command() as u8;
-        self.i2c
-            .write_read(Self::ADDRESS, &[command_address], &mut data)
-            .await?;
+        let register = CommandRegister::command();
+        self.bus_write_read(register, &mut data).await?;
         Ok(CommandRegister::new_with_raw_value(u16::from_le_bytes(
             data,
         )))
@@ -58,12 +53,149 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     pub(crate) async fn write_two_byte_command(
         &mut self,
         command: impl WriteTwoByteCommand,
-    ) -> Result<(), Ap33772sError> {
-        let command_address = command.get_command() as u8;
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let register = command.get_command();
         let data = command.raw_value().to_le_bytes();
-        self.i2c
-            .write(Self::ADDRESS, &[command_address, data[0], data[1]])
-            .await?;
+        self.bus_write(register, &[register as u8, data[0], data[1]])
+            .await
+    }
+
+    /// Writes a one byte command and, when write verification is enabled, reads the register back
+    /// to confirm the device accepted the value.
+    #[maybe_async::maybe_async]
+    pub(crate) async fn write_one_byte_command_verified(
+        &mut self,
+        command: impl WriteOneByteCommand,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let register = command.get_command();
+        let expected = command.raw_value();
+        self.write_one_byte_command(command).await?;
+        if self.write_verification {
+            let mut actual: [u8; 1] = [0x00];
+            self.read_raw_register(register, &mut actual).await?;
+            Self::verify_register(register, u16::from(expected), u16::from(actual[0]))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a two byte command and, when write verification is enabled, reads the register back
+    /// to confirm the device accepted the value.
+    #[maybe_async::maybe_async]
+    pub(crate) async fn write_two_byte_command_verified(
+        &mut self,
+        command: impl WriteTwoByteCommand,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let register = command.get_command();
+        let expected = command.raw_value();
+        self.write_two_byte_command(command).await?;
+        if self.write_verification {
+            let mut actual: [u8; 2] = [0x00; 2];
+            self.read_raw_register(register, &mut actual).await?;
+            Self::verify_register(register, expected, u16::from_le_bytes(actual))?;
+        }
+        Ok(())
+    }
+
+    /// Reads the raw bytes of a register. The length of `data` determines how many bytes are read,
+    /// so multi-byte registers such as [`Command::AllSourcesPowerDataObject`] can be read in one transfer.
+    #[maybe_async::maybe_async]
+    pub(crate) async fn read_raw_register(
+        &mut self,
+        command: Command,
+        data: &mut [u8],
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        self.bus_write_read(command, data).await
+    }
+
+    /// Writes the raw bytes of a one or two byte register
+    #[maybe_async::maybe_async]
+    pub(crate) async fn write_raw_register(
+        &mut self,
+        command: Command,
+        data: &[u8],
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        const MAX_REGISTER_WIDTH: usize = 2;
+        assert!(
+            data.len() <= MAX_REGISTER_WIDTH,
+            "Registers are at most two bytes wide"
+        );
+        let mut buffer = [0x00; MAX_REGISTER_WIDTH + 1];
+        buffer[0] = command as u8;
+        buffer[1..=data.len()].copy_from_slice(data);
+        self.bus_write(command, &buffer[..=data.len()]).await
+    }
+
+    /// Writes `bytes`, starting with the register address, retrying as set by [`Self::set_retry_policy`].
+    /// Writes to registers that are not [idempotent](Command::is_idempotent) are never retried.
+    /// Writes to registers frozen by [`Self::lock_configuration`] are refused.
+    #[maybe_async::maybe_async]
+    async fn bus_write(
+        &mut self,
+        command: Command,
+        bytes: &[u8],
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        self.ensure_unlocked(command)?;
+        let mut attempt = 1;
+        loop {
+            match self.i2c.write(Self::ADDRESS, bytes).await {
+                Ok(()) => return Ok(()),
+                Err(error) if command.is_idempotent() && self.should_retry(attempt, &error) => {
+                    attempt += 1;
+                    self.wait_before_retry().await;
+                }
+                Err(error) => return Err(Ap33772sError::bus(command, Operation::Write, error)),
+            }
+        }
+    }
+
+    /// Reads `data` from a register, retrying as set by [`Self::set_retry_policy`]
+    #[maybe_async::maybe_async]
+    async fn bus_write_read(
+        &mut self,
+        command: Command,
+        data: &mut [u8],
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let mut attempt = 1;
+        loop {
+            match self
+                .i2c
+                .write_read(Self::ADDRESS, &[command as u8], data)
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(error) if self.should_retry(attempt, &error) => {
+                    attempt += 1;
+                    self.wait_before_retry().await;
+                }
+                Err(error) => return Err(Ap33772sError::bus(command, Operation::Read, error)),
+            }
+        }
+    }
+
+    fn should_retry(&self, attempt: u8, error: &I2C::Error) -> bool {
+        attempt < self.retry_policy.attempts && self.retry_policy.is_retryable(error.kind())
+    }
+
+    #[maybe_async::maybe_async]
+    async fn wait_before_retry(&mut self) {
+        self.retry_count = self.retry_count.saturating_add(1);
+        self.delay
+            .delay_us(u32::try_from(self.retry_policy.delay.as_micros()).unwrap_or(u32::MAX))
+            .await;
+    }
+
+    fn verify_register(
+        command: Command,
+        expected: u16,
+        actual: u16,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        if expected != actual {
+            return Err(Ap33772sError::WriteVerificationFailed {
+                command,
+                expected,
+                actual,
+            });
+        }
         Ok(())
     }
 }
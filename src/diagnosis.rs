@@ -0,0 +1,270 @@
+//! # Negotiation Rejection Diagnosis
+//!
+//! The PD_MSGRLT register only reports that a request was [`Invalid`], [`NotSupported`] or that
+//! the [`TransactionFailed`], not why. [`diagnose_rejection`] compares the request that was sent
+//! against the source capabilities, the PDCONFIG enables and the Minimum Selection Voltage and
+//! lists the likely causes, for example an EPR PDO requested while EPR Mode is disabled.
+//!
+//! The causes are found without communicating with the device. [`Ap33772s::diagnose_rejection`]
+//! reads the registers they depend on first.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ap33772s_rs::Ap33772s;
+//! use ap33772s_rs::types::PowerRequest;
+//! use ap33772s_rs::types::command_structures::*;
+//! use ap33772s_rs::units::*;
+//!
+//! # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+//! let pdos = device.get_all_source_power_capabilities()?;
+//! let voltage = Some(ElectricPotential::new::<volt>(20.0));
+//! let current = OperatingCurrentSelection::_3A;
+//! let pdo = PowerDataObject::ExtendedPowerRange8;
+//!
+//! let response = device.negotiate_power_delivery(pdo, voltage, current, &pdos)?;
+//! if response != PowerDeliveryResponse::Success {
+//!     let request = PowerRequest::new(pdo, voltage, current);
+//!     for cause in device.diagnose_rejection(&request, response)?.iter() {
+//!         println!("{cause}");
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Invalid`]: crate::types::command_structures::PowerDeliveryResponse::Invalid
+//! [`NotSupported`]: crate::types::command_structures::PowerDeliveryResponse::NotSupported
+//! [`TransactionFailed`]: crate::types::command_structures::PowerDeliveryResponse::TransactionFailed
+//! [`Ap33772s::diagnose_rejection`]: crate::Ap33772s::diagnose_rejection
+use crate::errors::{Ap33772sError, RequestError};
+use crate::types::command_structures::{
+    AllSourceDataPowerDataObject, PowerDataObject, PowerDeliveryResponse, PowerType,
+};
+use crate::types::{PowerDeliveryMode, PowerRequest};
+use crate::units::*;
+
+/// The number of causes a [`RejectionDiagnosis`] can hold, one per check
+const MAXIMUM_CAUSES: usize = 5;
+
+/// A likely reason for a request being rejected, see [`diagnose_rejection`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RejectionCause {
+    /// No GoodCRC was received for the request, the CC connection or cable is unreliable
+    NoGoodCrc,
+    /// The requested PDO is not offered by the source
+    PowerDataObjectNotDetected,
+    /// An EPR PDO was requested but EPR_MODE_EN is disabled in PDCONFIG
+    ExtendedPowerRangeDisabled,
+    /// An EPR PDO was requested but EPR Mode was never entered, the source or cable does not
+    /// support EPR
+    ExtendedPowerRangeNotEntered,
+    /// A PPS or AVS PDO was requested but PPS_AVS_EN is disabled in PDCONFIG
+    AdjustableSupplyDisabled,
+    /// A PPS or AVS PDO was requested without a voltage
+    VoltageMissing,
+    /// The requested voltage is outside the range advertised by the PDO. All values are in
+    /// millivolts.
+    VoltageOutOfRange {
+        requested: f32,
+        minimum: f32,
+        maximum: f32,
+    },
+    /// The requested voltage is above the 21V or 28V the AP33772S supports. All values are in
+    /// millivolts.
+    AboveAbsoluteMaximumVoltage { requested: f32, maximum: f32 },
+    /// The requested current is above the maximum advertised by the PDO. All values are in
+    /// milliamperes.
+    CurrentAboveMaximum { requested: f32, maximum: f32 },
+    /// The requested voltage is below the Minimum Selection Voltage, so VOUT stays off even if
+    /// the request is accepted. All values are in millivolts.
+    BelowMinimumSelectionVoltage {
+        requested: f32,
+        minimum_selection_voltage: f32,
+    },
+}
+
+impl core::fmt::Display for RejectionCause {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RejectionCause::NoGoodCrc => write!(
+                f,
+                "No GoodCRC received from the source, check the CC connection and cable"
+            ),
+            RejectionCause::PowerDataObjectNotDetected => {
+                write!(f, "The requested PDO is not offered by the source")
+            }
+            RejectionCause::ExtendedPowerRangeDisabled => {
+                write!(f, "EPR PDO requested but EPR mode disabled in PDCONFIG")
+            }
+            RejectionCause::ExtendedPowerRangeNotEntered => write!(
+                f,
+                "EPR PDO requested but EPR mode was not entered, the source or cable does not support EPR"
+            ),
+            RejectionCause::AdjustableSupplyDisabled => {
+                write!(f, "PPS/AVS PDO requested but PPS/AVS disabled in PDCONFIG")
+            }
+            RejectionCause::VoltageMissing => {
+                write!(f, "PPS/AVS PDO requested without a voltage")
+            }
+            RejectionCause::VoltageOutOfRange {
+                requested,
+                minimum,
+                maximum,
+            } => write!(
+                f,
+                "Requested {requested} mV is outside the PDO range of {minimum} mV to {maximum} mV"
+            ),
+            RejectionCause::AboveAbsoluteMaximumVoltage { requested, maximum } => write!(
+                f,
+                "Requested {requested} mV is above the AP33772S maximum of {maximum} mV"
+            ),
+            RejectionCause::CurrentAboveMaximum { requested, maximum } => write!(
+                f,
+                "Requested {requested} mA is above the PDO maximum of {maximum} mA"
+            ),
+            RejectionCause::BelowMinimumSelectionVoltage {
+                requested,
+                minimum_selection_voltage,
+            } => write!(
+                f,
+                "Requested {requested} mV is below VSELMIN of {minimum_selection_voltage} mV, VOUT stays off"
+            ),
+        }
+    }
+}
+
+/// The likely causes of a rejected request, returned by [`diagnose_rejection`]. It is empty if no
+/// cause was found.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RejectionDiagnosis {
+    causes: [Option<RejectionCause>; MAXIMUM_CAUSES],
+}
+
+impl RejectionDiagnosis {
+    /// Returns the causes in the order they were found
+    pub fn iter(&self) -> impl Iterator<Item = &RejectionCause> {
+        self.causes.iter().flatten()
+    }
+
+    /// Returns `true` if no cause was found
+    pub fn is_empty(&self) -> bool {
+        self.causes.iter().all(Option::is_none)
+    }
+
+    /// Returns `true` if the cause was found
+    pub fn contains(&self, cause: &RejectionCause) -> bool {
+        self.iter().any(|found| found == cause)
+    }
+
+    fn push(&mut self, cause: RejectionCause) {
+        if let Some(slot) = self.causes.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(cause);
+        }
+    }
+}
+
+impl core::fmt::Display for RejectionDiagnosis {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No likely cause found");
+        }
+        for (index, cause) in self.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "- {cause}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the likely causes of a request being answered with `response`.
+///
+/// # Parameters
+///
+/// - `request`: The request that was sent
+/// - `response`: The response read from PD_MSGRLT
+/// - `data_objects`: The source capabilities the request was made against
+/// - `power_delivery_mode`: The PDCONFIG enables
+/// - `minimum_selection_voltage`: The Minimum Selection Voltage (VSELMIN)
+///
+/// # Returns
+///
+/// The [`RejectionDiagnosis`], empty if the request looks valid. A source may still reject a
+/// valid request, for example when its power budget is shared with another port.
+pub fn diagnose_rejection(
+    request: &PowerRequest,
+    response: PowerDeliveryResponse,
+    data_objects: &AllSourceDataPowerDataObject,
+    power_delivery_mode: &PowerDeliveryMode,
+    minimum_selection_voltage: ElectricPotential,
+) -> RejectionDiagnosis {
+    let mut diagnosis = RejectionDiagnosis::default();
+    if response == PowerDeliveryResponse::TransactionFailed {
+        diagnosis.push(RejectionCause::NoGoodCrc);
+    }
+
+    let power_data_object = request.power_data_object();
+    let data_object = data_objects.get_power_data_object(power_data_object);
+    if power_data_object >= PowerDataObject::ExtendedPowerRange8 {
+        if !power_delivery_mode.extended_power_range_mode_enabled {
+            diagnosis.push(RejectionCause::ExtendedPowerRangeDisabled);
+        } else if !data_objects.extended_power_range_entered() {
+            diagnosis.push(RejectionCause::ExtendedPowerRangeNotEntered);
+        }
+    }
+    if !data_object.is_detected() {
+        diagnosis.push(RejectionCause::PowerDataObjectNotDetected);
+        return diagnosis;
+    }
+
+    let adjustable = data_object.source_power_type() == PowerType::Adjustable;
+    if adjustable
+        && !power_delivery_mode.programmable_power_supply_adjustable_voltage_supply_enabled
+    {
+        diagnosis.push(RejectionCause::AdjustableSupplyDisabled);
+    }
+
+    match request.validate(data_objects) {
+        Err(Ap33772sError::InvalidRequest(RequestError::MissingArgument)) => {
+            diagnosis.push(RejectionCause::VoltageMissing)
+        }
+        Err(Ap33772sError::InvalidRequest(RequestError::VoltageOutOfRange {
+            requested,
+            minimum,
+            maximum,
+        })) => diagnosis.push(RejectionCause::VoltageOutOfRange {
+            requested,
+            minimum,
+            maximum,
+        }),
+        Err(Ap33772sError::InvalidRequest(RequestError::CurrentOutOfRange {
+            requested,
+            maximum,
+        })) => diagnosis.push(RejectionCause::CurrentAboveMaximum { requested, maximum }),
+        Err(Ap33772sError::InvalidRequest(RequestError::AboveAbsoluteMaximumVoltage {
+            requested,
+            maximum,
+        })) => diagnosis.push(RejectionCause::AboveAbsoluteMaximumVoltage { requested, maximum }),
+        _ => {}
+    }
+
+    let requested_voltage = match request.voltage() {
+        Some(voltage) if adjustable => Ok(voltage),
+        _ => data_object.get_max_voltage(),
+    };
+    match requested_voltage {
+        Ok(requested_voltage) if requested_voltage < minimum_selection_voltage => {
+            diagnosis.push(RejectionCause::BelowMinimumSelectionVoltage {
+                requested: requested_voltage.get::<millivolt>(),
+                minimum_selection_voltage: minimum_selection_voltage.get::<millivolt>(),
+            });
+        }
+        _ => {}
+    }
+    diagnosis
+}
@@ -1,14 +1,40 @@
 //! This Module contains all the public facing Errors that can occur when using this driver
-use crate::{hal, types::command_structures::PowerDataObject};
+use crate::{
+    hal,
+    types::command_structures::{Command, PowerDataObject, Status},
+    types::{PowerDataObjectKind, UnderVoltageThreshold},
+};
 
 /// Represents the different errors that can occur while interacting with the AP33772S device.
+///
+/// `E` is the error type of the I2C bus, so the error reported by the HAL is kept as is, e.g. the
+/// errno from `linux-embedded-hal` or the libftdi error from `ftdi-embedded-hal`. Errors raised
+/// without any communication with the device, such as those from
+/// [`PowerRequest::validate`](crate::types::PowerRequest::validate), use [`NoBusError`] and convert
+/// into the error of any bus with `?`.
 #[derive(PartialEq, Clone, Debug)]
 #[non_exhaustive]
-pub enum Ap33772sError {
-    /// Represents an I2C Error this is specifcally a low level bus communication error
-    I2c(hal::ErrorKind),
+pub enum Ap33772sError<E = NoBusError> {
+    /// Represents an I2C Error this is specifcally a low level bus communication error. The register
+    /// being accessed and the direction of the transfer are recorded alongside the bus error.
+    I2c {
+        /// The register that was being accessed
+        command: Command,
+        /// Whether the register was being read or written
+        operation: Operation,
+        /// The error reported by the HAL, its [`kind`](hal::Error::kind) gives the [`hal::ErrorKind`]
+        source: E,
+    },
     /// Represents a conversion error, this can happen if the data being converted is in the wrong scale/format
-    ConversionFailed,
+    ConversionFailed {
+        /// The register the value was being converted to or from, `None` if the conversion is not
+        /// tied to a register, e.g. evaluating a thermistor model. Values decoded from a PDO are
+        /// reported against [`Command::AllSourcesPowerDataObject`].
+        command: Option<Command>,
+        /// The value that could not be converted. Physical values are given in the unit of the
+        /// register (mV, mA, Ω or °C), values read from the device as their raw register value.
+        value: f32,
+    },
     /// Represents a data malformed error, this can happen if the data being received is
     /// not in the expected format. Usuaully will occur if a reserved bit is being used and
     /// the enum cannot represent the state correctly. The u8 inside the error represents the value that was not expected
@@ -25,6 +51,199 @@ pub enum Ap33772sError {
     /// This is a preemptive error that can occur when the user tries to negotiate with the device to use a Power Data Object that is not detected
     /// Inside this error contains the Power Data Object that was not detected
     PowerDataObjectNotDetected(PowerDataObject),
+    /// This can occur when write verification is enabled and the value read back from a register
+    /// does not match the value that was written to it. The raw register values are stored for diagnostics.
+    WriteVerificationFailed {
+        /// The register that was written
+        command: Command,
+        /// The raw value that was written to the register
+        expected: u16,
+        /// The raw value that was read back from the register
+        actual: u16,
+    },
+    /// This can occur when a transactional setter fails and the previous register values could not be
+    /// written back. The device may be left partially configured. Both the failure that started the
+    /// rollback and the failure of the rollback itself are recorded.
+    RollbackFailed {
+        /// The register named by the error that started the rollback, `None` if it names none or
+        /// the rollback followed a rejected power request
+        command: Option<Command>,
+        /// The bus error that started the rollback, `None` if the transaction failed for another
+        /// reason, such as a write verification mismatch or a rejected power request
+        source: Option<E>,
+        /// The first register that could not be restored
+        rollback_command: Command,
+        /// The bus error raised while restoring `rollback_command`, `None` if it failed for
+        /// another reason
+        rollback_source: Option<E>,
+    },
+    /// This can occur when building [`Thresholds`](crate::types::Thresholds) with values that cannot be
+    /// encoded in the threshold registers or that conflict with each other. These are checked before
+    /// any communication with the device.
+    InvalidThreshold(ThresholdError),
+    /// This can occur when waiting for the device to become ready and the STARTED and I2C_READY flags,
+    /// and NEWPDO when requested, have not all been seen before the timeout. The STATUS flags seen
+    /// while waiting are stored for diagnostics.
+    ReadyTimeout(Status),
+    /// This can occur when a PDO from 8 to 13 is requested, or EPR Mode is waited for, and the
+    /// source has not sent its EPR capabilities. EPR_MODE_EN may be disabled in PDCONFIG, or the
+    /// source or cable may not support EPR.
+    ExtendedPowerRangeNotEntered,
+    /// This can occur when a request or threshold would exceed the
+    /// [`SafetyLimits`](crate::types::SafetyLimits) the driver was created with. These are checked
+    /// before anything is written to the device.
+    SafetyLimitExceeded(SafetyLimitError),
+    /// This can occur when a safety relevant register is written while the configuration is
+    /// locked, see [`Ap33772s::lock_configuration`](crate::Ap33772s::lock_configuration). Nothing
+    /// is written to the device. The [`Command`] is the register that was refused.
+    ConfigurationLocked(Command),
+    /// This can occur when verifying a locked configuration and a register no longer holds the
+    /// value it had when the configuration was locked, for example after the device was reset.
+    /// The raw register values are stored for diagnostics.
+    LockedConfigurationChanged {
+        /// The register that changed
+        command: Command,
+        /// The raw value the register held when the configuration was locked
+        expected: u16,
+        /// The raw value read from the register
+        actual: u16,
+    },
+}
+
+/// The bus error type of errors raised without any communication with the device. It has no
+/// values, so an `Ap33772sError<NoBusError>` is never an [`Ap33772sError::I2c`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NoBusError {}
+
+impl<E: hal::Error> From<Ap33772sError<NoBusError>> for Ap33772sError<E> {
+    fn from(error: Ap33772sError<NoBusError>) -> Self {
+        match error {
+            Ap33772sError::I2c { source, .. } => match source {},
+            Ap33772sError::ConversionFailed { command, value } => {
+                Ap33772sError::ConversionFailed { command, value }
+            }
+            Ap33772sError::DataMalformed(value) => Ap33772sError::DataMalformed(value),
+            Ap33772sError::InvalidRequest(error) => Ap33772sError::InvalidRequest(error),
+            Ap33772sError::WrongCommandVersion(value) => Ap33772sError::WrongCommandVersion(value),
+            Ap33772sError::InitialisationFailure => Ap33772sError::InitialisationFailure,
+            Ap33772sError::PowerDataObjectNotDetected(power_data_object) => {
+                Ap33772sError::PowerDataObjectNotDetected(power_data_object)
+            }
+            Ap33772sError::WriteVerificationFailed {
+                command,
+                expected,
+                actual,
+            } => Ap33772sError::WriteVerificationFailed {
+                command,
+                expected,
+                actual,
+            },
+            Ap33772sError::RollbackFailed {
+                command,
+                rollback_command,
+                ..
+            } => Ap33772sError::RollbackFailed {
+                command,
+                source: None,
+                rollback_command,
+                rollback_source: None,
+            },
+            Ap33772sError::InvalidThreshold(error) => Ap33772sError::InvalidThreshold(error),
+            Ap33772sError::ReadyTimeout(status) => Ap33772sError::ReadyTimeout(status),
+            Ap33772sError::ExtendedPowerRangeNotEntered => {
+                Ap33772sError::ExtendedPowerRangeNotEntered
+            }
+            Ap33772sError::SafetyLimitExceeded(error) => Ap33772sError::SafetyLimitExceeded(error),
+            Ap33772sError::ConfigurationLocked(command) => {
+                Ap33772sError::ConfigurationLocked(command)
+            }
+            Ap33772sError::LockedConfigurationChanged {
+                command,
+                expected,
+                actual,
+            } => Ap33772sError::LockedConfigurationChanged {
+                command,
+                expected,
+                actual,
+            },
+        }
+    }
+}
+
+/// The direction of the I2C transfer that failed, see [`Ap33772sError::I2c`]
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Operation {
+    Read,
+    Write,
+}
+
+impl<E> Ap33772sError<E> {
+    /// Creates an [`Ap33772sError::ConversionFailed`] for a value being converted for a register
+    pub(crate) fn conversion(command: Command, value: f32) -> Self {
+        Ap33772sError::ConversionFailed {
+            command: Some(command),
+            value,
+        }
+    }
+
+    /// Attributes an [`Ap33772sError::ConversionFailed`] that is not tied to a register to `command`,
+    /// e.g. when a host-side model is evaluated to derive a register value
+    pub(crate) fn for_register(self, command: Command) -> Self {
+        match self {
+            Ap33772sError::ConversionFailed {
+                command: None,
+                value,
+            } => Ap33772sError::conversion(command, value),
+            error => error,
+        }
+    }
+
+    /// Creates an [`Ap33772sError::RollbackFailed`] from the error that started a rollback, if any,
+    /// and the error raised while restoring `rollback_command`
+    pub(crate) fn rollback_failed(
+        original: Option<Self>,
+        rollback_command: Command,
+        rollback: Self,
+    ) -> Self {
+        let command = original.as_ref().and_then(Ap33772sError::command);
+        Ap33772sError::RollbackFailed {
+            command,
+            source: original.and_then(Ap33772sError::into_bus_error),
+            rollback_command,
+            rollback_source: rollback.into_bus_error(),
+        }
+    }
+
+    /// Returns the register the error names, if any
+    fn command(&self) -> Option<Command> {
+        match self {
+            Ap33772sError::I2c { command, .. }
+            | Ap33772sError::WriteVerificationFailed { command, .. }
+            | Ap33772sError::ConfigurationLocked(command)
+            | Ap33772sError::LockedConfigurationChanged { command, .. } => Some(*command),
+            Ap33772sError::ConversionFailed { command, .. } => *command,
+            _ => None,
+        }
+    }
+
+    /// Creates an [`Ap33772sError::I2c`] from a bus error raised while accessing a register
+    pub(crate) fn bus(command: Command, operation: Operation, source: E) -> Self {
+        Ap33772sError::I2c {
+            command,
+            operation,
+            source,
+        }
+    }
+
+    /// Returns the bus error of an [`Ap33772sError::I2c`]
+    fn into_bus_error(self) -> Option<E> {
+        match self {
+            Ap33772sError::I2c { source, .. } => Some(source),
+            _ => None,
+        }
+    }
 }
 
 /// This Error is specifically an internal error that is used before communication with the device is taken.
@@ -34,23 +253,117 @@ pub enum Ap33772sError {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum RequestError {
     MissingArgument,
-    VoltageOutOfRange,
-    CurrentOutOfRange,
+    /// The voltage, after rounding to the PDO resolution, is outside the range of the PDO. All
+    /// values are in millivolts.
+    VoltageOutOfRange {
+        requested: f32,
+        minimum: f32,
+        maximum: f32,
+    },
+    /// The current is above the maximum current of the PDO. All values are in milliamperes.
+    CurrentOutOfRange {
+        requested: f32,
+        maximum: f32,
+    },
+    /// The voltage is above the highest voltage the AP33772S supports, 21V for Standard and 28V
+    /// for Extended Power Range PDOs. Sources may advertise EPR PDOs up to 48V. All values are in
+    /// millivolts.
+    AboveAbsoluteMaximumVoltage {
+        requested: f32,
+        maximum: f32,
+    },
 }
-impl<E: hal::Error> From<E> for Ap33772sError {
-    fn from(e: E) -> Self {
-        Ap33772sError::I2c(e.kind())
+/// Identifies the [`SafetyLimits`](crate::types::SafetyLimits) that would be exceeded, see
+/// [`Ap33772sError::SafetyLimitExceeded`]
+#[derive(PartialEq, Clone, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SafetyLimitError {
+    /// The voltage is above the maximum voltage. All values are in millivolts.
+    VoltageAboveLimit { requested: f32, maximum: f32 },
+    /// The current is above the maximum current. All values are in milliamperes.
+    CurrentAboveLimit { requested: f32, maximum: f32 },
+    /// The power is above the maximum power. All values are in watts.
+    PowerAboveLimit { requested: f32, maximum: f32 },
+    /// The kind of the requested PDO is not allowed
+    PowerDataObjectKindNotAllowed(PowerDataObjectKind),
+    /// The over voltage threshold is above the maximum offset. All values are in millivolts.
+    OverVoltageAboveLimit { requested: f32, maximum: f32 },
+    /// The under voltage threshold is a lower percentage of the requested voltage than the minimum
+    UnderVoltageBelowLimit {
+        requested: UnderVoltageThreshold,
+        minimum: UnderVoltageThreshold,
+    },
+    /// The over temperature or derating threshold is above the maximum temperature. All values
+    /// are in degrees Celsius.
+    TemperatureAboveLimit {
+        field: ThresholdField,
+        requested: f32,
+        maximum: f32,
+    },
+}
+
+/// This Error is returned by the [`ThresholdsBuilder`](crate::types::ThresholdsBuilder) and identifies
+/// which threshold was rejected.
+#[derive(PartialEq, Clone, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThresholdError {
+    /// The value is negative, not finite or cannot be encoded in the register of the field
+    OutOfRange(ThresholdField),
+    /// The derating temperature must be below the over temperature threshold, otherwise the device
+    /// shuts down before it derates. Both registers default to 120°C, so this is also returned when
+    /// neither field was set on the [`ThresholdsBuilder`](crate::types::ThresholdsBuilder)
+    DeratingNotBelowOverTemperature,
+    /// The over voltage threshold is an offset above the requested voltage, a zero offset places it at
+    /// the negotiated voltage and triggers protection during normal operation
+    OverVoltageNotAboveRequestedVoltage,
+}
+
+impl ThresholdError {
+    /// Returns the field of [`Thresholds`](crate::types::Thresholds) that was rejected
+    pub fn field(&self) -> ThresholdField {
+        match self {
+            ThresholdError::OutOfRange(field) => *field,
+            ThresholdError::DeratingNotBelowOverTemperature => ThresholdField::Derating,
+            ThresholdError::OverVoltageNotAboveRequestedVoltage => ThresholdField::OverVoltage,
+        }
     }
 }
 
+/// Identifies a field of [`Thresholds`](crate::types::Thresholds)
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThresholdField {
+    OverVoltage,
+    UnderVoltage,
+    OverCurrent,
+    OverTemperature,
+    Derating,
+}
+
 // Allows Error Bubbling when working with both std and no-std rust
-impl core::error::Error for Ap33772sError {}
+impl<E: core::fmt::Debug> core::error::Error for Ap33772sError<E> {}
 
-impl core::fmt::Display for Ap33772sError {
+impl<E: core::fmt::Debug> core::fmt::Display for Ap33772sError<E> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Ap33772sError::I2c(err) => write!(f, "I2C error: {err:?}"),
-            Ap33772sError::ConversionFailed => write!(f, "Conversion error"),
+            Ap33772sError::I2c {
+                command,
+                operation,
+                source,
+            } => write!(
+                f,
+                "I2C error during {operation:?} of {command:?}: {source:?}"
+            ),
+            Ap33772sError::ConversionFailed {
+                command: Some(command),
+                value,
+            } => write!(f, "Conversion error for {command:?}: {value}"),
+            Ap33772sError::ConversionFailed {
+                command: None,
+                value,
+            } => write!(f, "Conversion error: {value}"),
             Ap33772sError::DataMalformed(_value) => write!(f, "Malformed Data error"),
             Ap33772sError::WrongCommandVersion(value) => {
                 write!(
@@ -66,33 +379,78 @@ impl core::fmt::Display for Ap33772sError {
                     "Power Data Object not detected on source: {power_data_object:?}"
                 )
             }
+            Ap33772sError::WriteVerificationFailed {
+                command,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Write verification failed for {command:?}: expected {expected:#06x}, read back {actual:#06x}"
+                )
+            }
+            Ap33772sError::RollbackFailed {
+                command,
+                source,
+                rollback_command,
+                rollback_source,
+            } => {
+                write!(
+                    f,
+                    "Failed to restore {rollback_command:?} ({rollback_source:?}) after a failed write to {command:?} ({source:?}), device may be partially configured"
+                )
+            }
+            Ap33772sError::InvalidThreshold(err) => write!(f, "Invalid threshold: {err:?}"),
+            Ap33772sError::ReadyTimeout(status) => write!(
+                f,
+                "Timed out waiting for the device to become ready, STATUS flags seen: {:#04x}",
+                status.raw_value()
+            ),
+            Ap33772sError::ExtendedPowerRangeNotEntered => write!(
+                f,
+                "EPR mode has not been entered, the source has not sent EPR capabilities"
+            ),
+            Ap33772sError::SafetyLimitExceeded(err) => write!(f, "Safety limit exceeded: {err:?}"),
+            Ap33772sError::ConfigurationLocked(command) => {
+                write!(f, "Configuration is locked, {command:?} cannot be written")
+            }
+            Ap33772sError::LockedConfigurationChanged {
+                command,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Locked register {command:?} changed: expected {expected:#06x}, read back {actual:#06x}"
+                )
+            }
         }
     }
 }
 
 #[cfg(feature = "defmt")]
-impl defmt::Format for Ap33772sError {
+impl<E: core::fmt::Debug> defmt::Format for Ap33772sError<E> {
     fn format(&self, f: defmt::Formatter) {
-        use crate::hal::Error;
-        use crate::hal::ErrorKind;
         defmt::write!(
             f,
             "AP33772S Error: {}",
             match self {
-                Ap33772sError::I2c(err) => {
-                    // Convert the ErrorKind into a string for defmt
-                    let kind_str = match err.kind() {
-                        ErrorKind::Bus => "Bus",
-                        ErrorKind::ArbitrationLoss => "ArbitrationLoss",
-                        ErrorKind::NoAcknowledge(_) => "NoAcknowledge",
-                        ErrorKind::Overrun => "Overrun",
-                        ErrorKind::Other => "Other",
-                        _ => "Unknown",
-                    };
-                    defmt::write!(f, "AP33772S Error: I2C error ({})", kind_str);
+                Ap33772sError::I2c {
+                    command,
+                    operation,
+                    source,
+                } => {
+                    defmt::write!(
+                        f,
+                        "AP33772S Error: I2C error during {:?} of {:?} ({})",
+                        operation,
+                        command,
+                        defmt::Debug2Format(source)
+                    );
                 }
 
-                Ap33772sError::ConversionFailed => defmt::write!(f, "Conversion error"),
+                Ap33772sError::ConversionFailed { command, value } =>
+                    defmt::write!(f, "Conversion error for {:?}: {}", command, value),
                 Ap33772sError::DataMalformed(value) =>
                     defmt::write!(f, "Malformed Data error: {:?}", value),
                 Ap33772sError::WrongCommandVersion(value) => {
@@ -113,6 +471,65 @@ impl defmt::Format for Ap33772sError {
                         power_data_object
                     )
                 }
+                Ap33772sError::WriteVerificationFailed {
+                    command,
+                    expected,
+                    actual,
+                } => {
+                    defmt::write!(
+                        f,
+                        "Write verification failed for {:?}: expected {=u16:#x}, read back {=u16:#x}",
+                        command,
+                        expected,
+                        actual
+                    )
+                }
+                Ap33772sError::RollbackFailed {
+                    command,
+                    source,
+                    rollback_command,
+                    rollback_source,
+                } => {
+                    defmt::write!(
+                        f,
+                        "Failed to restore {:?} ({}) after a failed write to {:?} ({}), device may be partially configured",
+                        rollback_command,
+                        defmt::Debug2Format(rollback_source),
+                        command,
+                        defmt::Debug2Format(source)
+                    )
+                }
+                Ap33772sError::InvalidThreshold(err) =>
+                    defmt::write!(f, "Invalid threshold: {:?}", err),
+                Ap33772sError::ReadyTimeout(status) => defmt::write!(
+                    f,
+                    "Timed out waiting for the device to become ready, STATUS flags seen: {=u8:#x}",
+                    status.raw_value()
+                ),
+                Ap33772sError::ExtendedPowerRangeNotEntered => defmt::write!(
+                    f,
+                    "EPR mode has not been entered, the source has not sent EPR capabilities"
+                ),
+                Ap33772sError::SafetyLimitExceeded(err) =>
+                    defmt::write!(f, "Safety limit exceeded: {:?}", err),
+                Ap33772sError::ConfigurationLocked(command) => defmt::write!(
+                    f,
+                    "Configuration is locked, {:?} cannot be written",
+                    command
+                ),
+                Ap33772sError::LockedConfigurationChanged {
+                    command,
+                    expected,
+                    actual,
+                } => {
+                    defmt::write!(
+                        f,
+                        "Locked register {:?} changed: expected {=u16:#x}, read back {=u16:#x}",
+                        command,
+                        expected,
+                        actual
+                    )
+                }
             }
         );
     }
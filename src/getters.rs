@@ -12,6 +12,7 @@ use crate::commands::data_objects::all_source_power_data_object::MAX_STANDARD_PO
 use crate::commands::data_objects::extended_power_range_data_object::ExtendedPowerRangeDataObject;
 use crate::commands::data_objects::source_power_range_data_object::SourcePowerRangeDataObject;
 use crate::commands::data_objects::standard_power_range_data_object::StandardPowerRangeDataObject;
+use crate::commands::misc::gpio::Gpio;
 use crate::commands::power_delivery::power_delivery_message_result::PowerDeliveryMessageResult;
 use crate::commands::requested::current_requested::CurrentRequested;
 use crate::commands::requested::voltage_requested::VoltageRequested;
@@ -28,12 +29,17 @@ use crate::commands::thresholds::over_current_protection_threshold::OverCurrentP
 use crate::commands::thresholds::over_temperature_protection_threshold::OverTemperatureProtectionThreshold;
 use crate::commands::thresholds::over_voltage_protection_threshold::OverVoltageProtectionThreshold;
 use crate::commands::thresholds::under_voltage_protection_threshold::UnderVoltageProtectionThreshold;
+use crate::commands::thresholds::vdc_threshold::VDCTHR;
+use crate::diagnosis::{RejectionDiagnosis, diagnose_rejection};
 
 // Public API Types
 use crate::types::command_structures::*;
 use crate::types::*;
 use crate::units::*;
 
+/// The combined width of the VOLTAGE, CURRENT, TEMP, VREQ and IREQ registers
+const STATISTICS_BURST_LENGTH: usize = 8;
+
 impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
     /// Reads the current device status register.
     ///
@@ -48,7 +54,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::Ap33772s;
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// let status = device.get_status()?;
     ///
     /// if status.i2c_ready() {
@@ -64,7 +70,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`Status`]: crate::types::command_structures::Status
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_status(&mut self) -> Result<Status, Ap33772sError> {
+    pub async fn get_status(&mut self) -> Result<Status, Ap33772sError<I2C::Error>> {
         self.read_one_byte_command::<Status>().await
     }
 
@@ -80,7 +86,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`OperationMode`]: crate::types::command_structures::OperationMode
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_operating_mode(&mut self) -> Result<OperationMode, Ap33772sError> {
+    pub async fn get_operating_mode(&mut self) -> Result<OperationMode, Ap33772sError<I2C::Error>> {
         self.read_one_byte_command::<OperationMode>().await
     }
 
@@ -97,7 +103,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::Ap33772s;
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// let pd_config = device.get_power_delivery_configuration()?;
     ///
     /// if pd_config.programmable_power_supply_adjustable_voltage_supply_enabled {
@@ -115,7 +121,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     #[maybe_async::maybe_async]
     pub async fn get_power_delivery_configuration(
         &mut self,
-    ) -> Result<PowerDeliveryMode, Ap33772sError> {
+    ) -> Result<PowerDeliveryMode, Ap33772sError<I2C::Error>> {
         let command_result = self
             .read_one_byte_command::<PowerDeliveryConfiguration>()
             .await?;
@@ -142,7 +148,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// ```rust
     /// # use ap33772s_rs::Ap33772s;
     /// # use ap33772s_rs::units::*;
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// let stats = device.get_statistics()?;
     ///
     /// println!("Current operation: {:.2}V @ {:.2}A = {:.2}W",
@@ -154,13 +160,91 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// # }
     /// ```
     ///
-    /// **Note**: This method performs multiple I2C reads. For better performance,
-    /// use individual getter methods if you only need specific measurements.
+    /// **Note**: The statistics registers are read in a single I2C transfer when the device supports
+    /// burst reads, giving a snapshot taken at one instant. Otherwise each register is read
+    /// separately. See [`BurstRead`] for how this is detected.
     ///
     /// [`Statistics`]: crate::types::Statistics
+    /// [`BurstRead`]: crate::types::BurstRead
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_statistics(&mut self) -> Result<Statistics, Ap33772sError> {
+    pub async fn get_statistics(&mut self) -> Result<Statistics, Ap33772sError<I2C::Error>> {
+        match self.statistics_burst_read {
+            BurstRead::Enabled => {
+                let data = self.read_statistics_registers().await?;
+                Self::decode_statistics(&data)
+            }
+            BurstRead::Disabled => self.read_statistics_individually().await,
+            BurstRead::Detect => self.detect_statistics_burst_read().await,
+        }
+    }
+
+    /// Reads the VOLTAGE, CURRENT, TEMP, VREQ and IREQ registers in one transfer starting at VOLTAGE
+    #[maybe_async::maybe_async]
+    async fn read_statistics_registers(
+        &mut self,
+    ) -> Result<[u8; STATISTICS_BURST_LENGTH], Ap33772sError<I2C::Error>> {
+        let mut data = [0x00; STATISTICS_BURST_LENGTH];
+        self.read_raw_register(Command::Voltage, &mut data).await?;
+        Ok(data)
+    }
+
+    fn decode_statistics(
+        data: &[u8; STATISTICS_BURST_LENGTH],
+    ) -> Result<Statistics, Ap33772sError<I2C::Error>> {
+        let voltage =
+            Voltage::new_with_raw_value(u16::from_le_bytes([data[0], data[1]])).voltage()?;
+        let current = Current::new_with_raw_value(data[2]).current()?;
+        let temperature = Temperature::new_with_raw_value(data[3]).temperature();
+        let requested_voltage =
+            VoltageRequested::new_with_raw_value(u16::from_le_bytes([data[4], data[5]]))
+                .voltage()?;
+        let requested_current =
+            CurrentRequested::new_with_raw_value(u16::from_le_bytes([data[6], data[7]]))
+                .current()?;
+        Ok(Statistics {
+            current,
+            voltage,
+            temperature,
+            power: current * voltage,
+            requested_voltage,
+            requested_current,
+            requested_power: requested_voltage * requested_current,
+        })
+    }
+
+    /// Performs a burst read and checks the VREQ and IREQ bytes against individual reads of those
+    /// registers, which only match if the register address auto-increments. The measured VOLTAGE,
+    /// CURRENT and TEMP are not compared as they may change between the transfers. The outcome is
+    /// stored so detection only runs once, unless a bus error leaves it undetermined.
+    #[maybe_async::maybe_async]
+    async fn detect_statistics_burst_read(
+        &mut self,
+    ) -> Result<Statistics, Ap33772sError<I2C::Error>> {
+        let data = match self.read_statistics_registers().await {
+            Ok(data) => data,
+            // A transient bus error says nothing about auto-increment, detect again next time
+            Err(Ap33772sError::I2c { .. }) => return self.read_statistics_individually().await,
+            Err(other) => return Err(other),
+        };
+        let requested_voltage = self.read_two_byte_command::<VoltageRequested>().await?;
+        let requested_current = self.read_two_byte_command::<CurrentRequested>().await?;
+
+        let auto_increments = data[4..6] == requested_voltage.raw_value().to_le_bytes()
+            && data[6..8] == requested_current.raw_value().to_le_bytes();
+        if auto_increments {
+            self.statistics_burst_read = BurstRead::Enabled;
+            Self::decode_statistics(&data)
+        } else {
+            self.statistics_burst_read = BurstRead::Disabled;
+            self.read_statistics_individually().await
+        }
+    }
+
+    #[maybe_async::maybe_async]
+    async fn read_statistics_individually(
+        &mut self,
+    ) -> Result<Statistics, Ap33772sError<I2C::Error>> {
         let current = self.get_current().await?;
         let voltage = self.get_voltage().await?;
         let temperature = self.get_temperature().await?;
@@ -184,7 +268,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     #[cfg_attr(feature = "advanced", visibility::make(pub))]
     pub(crate) async fn get_power_delivery_request_result(
         &mut self,
-    ) -> Result<PowerDeliveryResponse, Ap33772sError> {
+    ) -> Result<PowerDeliveryResponse, Ap33772sError<I2C::Error>> {
         let power_delivery_request_result = self
             .read_one_byte_command::<PowerDeliveryMessageResult>()
             .await?;
@@ -193,6 +277,27 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
             .response()
             .map_err(|_| Ap33772sError::DataMalformed(power_delivery_request_result.raw_value()))
     }
+
+    /// Reads the VDCTHR register, the allowed difference between the requested and measured
+    /// output voltage.
+    ///
+    /// **Note**: This register is undocumented and the EVB user guide lists it as removed in
+    /// firmware 1.1.
+    #[maybe_async::maybe_async]
+    pub async fn get_vdc_threshold(&mut self) -> Result<Ratio, Ap33772sError<I2C::Error>> {
+        let vdc_threshold = self.read_one_byte_command::<VDCTHR>().await?;
+        Ok(vdc_threshold.threshold())
+    }
+
+    /// Reads the raw GPIO register.
+    ///
+    /// **Note**: No document describes the bits of this register, so the byte is returned
+    /// undecoded.
+    #[maybe_async::maybe_async]
+    pub async fn get_gpio(&mut self) -> Result<u8, Ap33772sError<I2C::Error>> {
+        let gpio = self.read_one_byte_command::<Gpio>().await?;
+        Ok(gpio.value())
+    }
 }
 
 impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
@@ -210,7 +315,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     #[maybe_async::maybe_async]
     pub async fn get_voltage_out_override(
         &mut self,
-    ) -> Result<VoltageOutputControl, Ap33772sError> {
+    ) -> Result<VoltageOutputControl, Ap33772sError<I2C::Error>> {
         let system_control = self.read_one_byte_command::<SystemControl>().await?;
         system_control
             .v_out_control()
@@ -226,9 +331,9 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`ElectricCurrent`]: crate::units::ElectricCurrent
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_current(&mut self) -> Result<ElectricCurrent, Ap33772sError> {
+    pub async fn get_current(&mut self) -> Result<ElectricCurrent, Ap33772sError<I2C::Error>> {
         let current = self.read_one_byte_command::<Current>().await?;
-        current.current()
+        Ok(current.current()?)
     }
 
     /// Reads the output voltage of the device.
@@ -240,9 +345,9 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`ElectricPotential`]: crate::units::ElectricPotential
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_voltage(&mut self) -> Result<ElectricPotential, Ap33772sError> {
+    pub async fn get_voltage(&mut self) -> Result<ElectricPotential, Ap33772sError<I2C::Error>> {
         let voltage = self.read_two_byte_command::<Voltage>().await?;
-        voltage.voltage()
+        Ok(voltage.voltage()?)
     }
 
     /// Reads the internal temperature of the device.
@@ -254,7 +359,9 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`ThermodynamicTemperature`]: crate::units::ThermodynamicTemperature
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_temperature(&mut self) -> Result<ThermodynamicTemperature, Ap33772sError> {
+    pub async fn get_temperature(
+        &mut self,
+    ) -> Result<ThermodynamicTemperature, Ap33772sError<I2C::Error>> {
         let temperature = self.read_one_byte_command::<Temperature>().await?;
         Ok(temperature.temperature())
     }
@@ -274,7 +381,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     /// [`get_statistics`]: Self::get_statistics
     #[maybe_async::maybe_async]
-    pub async fn get_power(&mut self) -> Result<Power, Ap33772sError> {
+    pub async fn get_power(&mut self) -> Result<Power, Ap33772sError<I2C::Error>> {
         let current = self.get_current().await?;
         let voltage = self.get_voltage().await?;
         let power = current * voltage;
@@ -289,9 +396,11 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`ElectricPotential`]: crate::units::ElectricPotential
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_requested_voltage(&mut self) -> Result<ElectricPotential, Ap33772sError> {
+    pub async fn get_requested_voltage(
+        &mut self,
+    ) -> Result<ElectricPotential, Ap33772sError<I2C::Error>> {
         let requested_voltage = self.read_two_byte_command::<VoltageRequested>().await?;
-        requested_voltage.voltage()
+        Ok(requested_voltage.voltage()?)
     }
     /// Reads the current requested by the connected device during power delivery negotiation.
     ///
@@ -302,9 +411,11 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`ElectricCurrent`]: crate::units::ElectricCurrent
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_requested_current(&mut self) -> Result<ElectricCurrent, Ap33772sError> {
+    pub async fn get_requested_current(
+        &mut self,
+    ) -> Result<ElectricCurrent, Ap33772sError<I2C::Error>> {
         let requested_current = self.read_two_byte_command::<CurrentRequested>().await?;
-        requested_current.current()
+        Ok(requested_current.current()?)
     }
     /// Calculates the power requested by the connected device during power delivery negotiation.
     ///
@@ -321,7 +432,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     /// [`get_statistics`]: Self::get_statistics
     #[maybe_async::maybe_async]
-    pub async fn get_requested_power(&mut self) -> Result<Power, Ap33772sError> {
+    pub async fn get_requested_power(&mut self) -> Result<Power, Ap33772sError<I2C::Error>> {
         let requested_voltage = self.get_requested_voltage().await?;
         let requested_current = self.get_requested_current().await?;
         let requested_power = requested_voltage * requested_current;
@@ -342,11 +453,11 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     #[maybe_async::maybe_async]
     pub async fn get_minimum_selection_voltage(
         &mut self,
-    ) -> Result<ElectricPotential, Ap33772sError> {
+    ) -> Result<ElectricPotential, Ap33772sError<I2C::Error>> {
         let voltage_selection = self
             .read_one_byte_command::<MinimumSelectionVoltage>()
             .await?;
-        voltage_selection.voltage()
+        Ok(voltage_selection.voltage()?)
     }
 }
 
@@ -366,7 +477,9 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`ThermalResistances`]: crate::types::ThermalResistances
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_thermal_resistances(&mut self) -> Result<ThermalResistances, Ap33772sError> {
+    pub async fn get_thermal_resistances(
+        &mut self,
+    ) -> Result<ThermalResistances, Ap33772sError<I2C::Error>> {
         let resistance_25 = self.read_two_byte_command::<ThermalResistance25>().await?;
         let resistance_50 = self.read_two_byte_command::<ThermalResistance50>().await?;
         let resistance_75 = self.read_two_byte_command::<ThermalResistance75>().await?;
@@ -393,7 +506,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// [`Thresholds`]: crate::types::Thresholds
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
-    pub async fn get_thresholds(&mut self) -> Result<Thresholds, Ap33772sError> {
+    pub async fn get_thresholds(&mut self) -> Result<Thresholds, Ap33772sError<I2C::Error>> {
         let over_voltage_threshold = self
             .read_one_byte_command::<OverVoltageProtectionThreshold>()
             .await?;
@@ -406,9 +519,9 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
         let under_voltage_threshold = self
             .read_one_byte_command::<UnderVoltageProtectionThreshold>()
             .await?;
-        let under_voltage_threshold = under_voltage_threshold
-            .threshold()
-            .map_err(|_| Ap33772sError::DataMalformed(under_voltage_threshold.raw_value()))?;
+        let under_voltage_threshold = under_voltage_threshold.threshold().map_err(|_| {
+            Ap33772sError::<I2C::Error>::DataMalformed(under_voltage_threshold.raw_value())
+        })?;
         let de_rating_threshold = self.read_one_byte_command::<DeRatingThreshold>().await?;
         Ok(Thresholds {
             over_voltage: over_voltage_threshold.voltage()?,
@@ -433,7 +546,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::Ap33772s;
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// let capabilities = device.get_all_source_power_capabilities()?;
     ///
     /// println!("Available power capabilities:");
@@ -449,16 +562,11 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     #[maybe_async::maybe_async]
     pub async fn get_all_source_power_capabilities(
         &mut self,
-    ) -> Result<AllSourceDataPowerDataObject, Ap33772sError> {
+    ) -> Result<AllSourceDataPowerDataObject, Ap33772sError<I2C::Error>> {
         const NUM_SOURCE_DATA_POWER_OBJECT_BYTES: usize = 26;
         let mut buff: [u8; NUM_SOURCE_DATA_POWER_OBJECT_BYTES] =
             [0; NUM_SOURCE_DATA_POWER_OBJECT_BYTES];
-        self.i2c
-            .write_read(
-                Self::ADDRESS,
-                &[Command::AllSourcesPowerDataObject as u8],
-                &mut buff,
-            )
+        self.read_raw_register(Command::AllSourcesPowerDataObject, &mut buff)
             .await?;
         let mut data_object = AllSourceDataPowerDataObject::default();
 
@@ -483,4 +591,124 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
 
         Ok(data_object)
     }
+
+    /// Identifies the Power Data Object the device is currently using.
+    ///
+    /// The requested voltage and current are cross-referenced with the source capabilities, see
+    /// [`ActiveContract`] for how the PDO is matched.
+    ///
+    /// # Returns
+    ///
+    /// The [`ActiveContract`], `None` if no Power Delivery source is connected, nothing has been
+    /// requested yet or no advertised PDO matches the requested voltage, or [`Ap33772sError`] on
+    /// communication error.
+    ///
+    /// **Note**: This method reads OPMODE, VREQ, IREQ and the 26 byte source capabilities block.
+    ///
+    /// [`ActiveContract`]: crate::types::ActiveContract
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    #[maybe_async::maybe_async]
+    pub async fn get_active_contract(
+        &mut self,
+    ) -> Result<Option<ActiveContract>, Ap33772sError<I2C::Error>> {
+        if !self
+            .get_operating_mode()
+            .await?
+            .power_delivery_source_connected()
+        {
+            return Ok(None);
+        }
+        let voltage = self.get_requested_voltage().await?;
+        if voltage.get::<millivolt>() == 0.0 {
+            return Ok(None);
+        }
+        let current = self.get_requested_current().await?;
+        let data_objects = self.get_all_source_power_capabilities().await?;
+        Ok(ActiveContract::infer(voltage, current, &data_objects))
+    }
+
+    /// Lists the likely causes of a request being rejected by the source.
+    ///
+    /// Reads the source capabilities, PDCONFIG and VSELMIN and compares them against the request,
+    /// see [`diagnose_rejection`] for the checks made.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: The request that was sent, with the same arguments as given to
+    ///   [`Self::negotiate_power_delivery`]
+    /// - `response`: The response returned by the negotiation
+    ///
+    /// # Returns
+    ///
+    /// The [`RejectionDiagnosis`], or [`Ap33772sError`] on communication error.
+    ///
+    /// [`diagnose_rejection`]: crate::diagnosis::diagnose_rejection
+    /// [`RejectionDiagnosis`]: crate::diagnosis::RejectionDiagnosis
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    #[maybe_async::maybe_async]
+    pub async fn diagnose_rejection(
+        &mut self,
+        request: &PowerRequest,
+        response: PowerDeliveryResponse,
+    ) -> Result<RejectionDiagnosis, Ap33772sError<I2C::Error>> {
+        let data_objects = self.get_all_source_power_capabilities().await?;
+        let power_delivery_mode = self.get_power_delivery_configuration().await?;
+        let minimum_selection_voltage = self.get_minimum_selection_voltage().await?;
+        Ok(diagnose_rejection(
+            request,
+            response,
+            &data_objects,
+            &power_delivery_mode,
+            minimum_selection_voltage,
+        ))
+    }
+
+    /// Reads a single Power Data Object from its SRC_SPR_PDOx or SRC_EPR_PDOx register.
+    ///
+    /// This is a two byte read, making it much cheaper than
+    /// [`get_all_source_power_capabilities`](Self::get_all_source_power_capabilities) when only
+    /// one PDO needs to be re-checked, for example in a polling loop.
+    ///
+    /// # Returns
+    ///
+    /// [`SourcePowerRangeDataObject::Standard`] for PDOs 1 to 7 and
+    /// [`SourcePowerRangeDataObject::Extended`] for PDOs 8 to 13, or [`Ap33772sError`] on
+    /// communication error. Check `is_detected()` to see if the source offers the PDO.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::Ap33772s;
+    /// # use ap33772s_rs::types::command_structures::PowerDataObject;
+    /// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pdo = device.get_source_power_capability(PowerDataObject::StandardPowerRange4)?;
+    /// if pdo.is_detected() {
+    ///     println!("PDO 4: {pdo}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`SourcePowerRangeDataObject::Standard`]: crate::types::command_structures::SourcePowerRangeDataObject::Standard
+    /// [`SourcePowerRangeDataObject::Extended`]: crate::types::command_structures::SourcePowerRangeDataObject::Extended
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    #[maybe_async::maybe_async]
+    pub async fn get_source_power_capability(
+        &mut self,
+        power_data_object: PowerDataObject,
+    ) -> Result<SourcePowerRangeDataObject, Ap33772sError<I2C::Error>> {
+        let mut buff = [0x00; 2];
+        self.read_raw_register(Command::from(power_data_object), &mut buff)
+            .await?;
+        let raw_value = u16::from_le_bytes(buff);
+        if usize::from(power_data_object) > MAX_STANDARD_POWER_DATA_OBJECTS {
+            Ok(SourcePowerRangeDataObject::Extended(
+                ExtendedPowerRangeDataObject::new_with_raw_value(raw_value),
+            ))
+        } else {
+            Ok(SourcePowerRangeDataObject::Standard(
+                StandardPowerRangeDataObject::new_with_raw_value(raw_value),
+            ))
+        }
+    }
 }
@@ -35,7 +35,7 @@
 //! ```rust
 //! use ap33772s_rs::{Ap33772s, types::Statistics};
 //! use ap33772s_rs::units::*;
-//! # async fn example(i2c: impl embedded_hal::i2c::I2c, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+//! # async fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
 //! // Create and initialize the driver
 //! let mut ap33772s = Ap33772s::new_default(i2c, delay)?;
 //!
@@ -69,9 +69,13 @@ compile_error!("You must enable either the sync or async feature. Please choose
 
 mod ap33772s;
 pub use ap33772s::Ap33772s;
+pub mod diagnosis;
 mod errors;
 mod getters;
+pub mod monitor;
+pub mod selection;
 mod setters;
+pub mod thermistor;
 pub mod types;
 pub mod units;
 
@@ -100,6 +104,7 @@ mod hal {
     pub use embedded_hal::i2c::Error;
     pub use embedded_hal::i2c::ErrorKind;
     pub use embedded_hal::i2c::I2c;
+    pub use embedded_hal::i2c::NoAcknowledgeSource;
     pub use embedded_hal::i2c::SevenBitAddress;
 }
 
@@ -112,5 +117,6 @@ mod hal {
     pub use embedded_hal_async::i2c::Error;
     pub use embedded_hal_async::i2c::ErrorKind;
     pub use embedded_hal_async::i2c::I2c;
+    pub use embedded_hal_async::i2c::NoAcknowledgeSource;
     pub use embedded_hal_async::i2c::SevenBitAddress;
 }
@@ -0,0 +1,169 @@
+//! # Source Attachment Monitoring
+//!
+//! The AP33772S reports whether a source is connected in the OPMODE register and flags newly
+//! received source capabilities in the STATUS register, but it has no notion of attach and detach
+//! over time. Without the INT line these changes can only be found by polling.
+//!
+//! [`SourceMonitor`] keeps the state seen on the previous poll and turns the difference into
+//! [`SourceEvent`]s, so firmware without an interrupt line can react to a charger being plugged in,
+//! unplugged or changing its capabilities.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ap33772s_rs::Ap33772s;
+//! use ap33772s_rs::monitor::{SourceEvent, SourceMonitor};
+//!
+//! # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+//! let mut monitor = SourceMonitor::new();
+//! loop {
+//!     while let Some(event) = monitor.poll(&mut device)? {
+//!         match event {
+//!             SourceEvent::Attached { kind, orientation } => {
+//!                 println!("{kind:?} source attached on {orientation:?}")
+//!             }
+//!             SourceEvent::Detached => println!("Source detached"),
+//!             SourceEvent::CapabilitiesChanged => {
+//!                 let capabilities = device.get_all_source_power_capabilities()?;
+//!                 println!("{capabilities}");
+//!             }
+//!         }
+//!     }
+//!     // Sleep until the next poll
+//! #   break;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::ap33772s::Ap33772s;
+use crate::hal::*;
+use crate::types::Ap33772sError;
+use crate::types::command_structures::{ConfigurationChannel, Status};
+
+/// The type of source that is connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SourceKind {
+    /// A source without USB Power Delivery, the output is fixed at 5V
+    ///
+    /// Datasheet Name: LGCYMOD
+    Legacy,
+    /// A USB Power Delivery source
+    ///
+    /// Datasheet Name: PDMOD
+    PowerDelivery,
+}
+
+/// The CC line the source was detected on, which gives the orientation of the USB-C plug
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Orientation {
+    Cc1,
+    Cc2,
+}
+
+impl From<ConfigurationChannel> for Orientation {
+    fn from(value: ConfigurationChannel) -> Self {
+        match value {
+            ConfigurationChannel::One => Orientation::Cc1,
+            ConfigurationChannel::Two => Orientation::Cc2,
+        }
+    }
+}
+
+/// A change in the connected source, see [`SourceMonitor::poll`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SourceEvent {
+    /// A source was connected, or the type or orientation of the connected source changed
+    Attached {
+        kind: SourceKind,
+        orientation: Orientation,
+    },
+    /// The source was disconnected
+    Detached,
+    /// New source capabilities were received from a Power Delivery source
+    CapabilitiesChanged,
+}
+
+/// Tracks source attachment by diffing successive OPMODE and STATUS reads.
+///
+/// **Note**: STATUS is cleared every time it is read, so the protection flags it also carries are
+/// consumed by [`Self::poll`]. They are kept in [`Self::last_status`] for inspection.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMonitor {
+    attached: Option<(SourceKind, Orientation)>,
+    pending: Option<SourceEvent>,
+    last_status: Status,
+}
+
+impl SourceMonitor {
+    /// Creates a monitor that assumes no source is attached, so a source that is already
+    /// connected is reported as [`SourceEvent::Attached`] on the first poll
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the source seen on the last poll, `None` if no source was connected
+    pub fn attached(&self) -> Option<(SourceKind, Orientation)> {
+        self.attached
+    }
+
+    /// Returns the STATUS flags read on the last poll
+    pub fn last_status(&self) -> Status {
+        self.last_status
+    }
+
+    /// Reads OPMODE and STATUS and returns the next event, or `None` if nothing changed.
+    ///
+    /// A single poll can produce two events, for example a Power Delivery source that attaches and
+    /// sends its capabilities between polls. The second event is returned by the next call
+    /// without any I2C traffic, so calling this in a `while let` loop drains every event.
+    #[maybe_async::maybe_async]
+    pub async fn poll<I2C: I2c, D: DelayNs>(
+        &mut self,
+        device: &mut Ap33772s<I2C, D>,
+    ) -> Result<Option<SourceEvent>, Ap33772sError<I2C::Error>> {
+        if let Some(event) = self.pending.take() {
+            return Ok(Some(event));
+        }
+        let operation_mode = device.get_operating_mode().await?;
+        self.last_status = device.get_status().await?;
+
+        let kind = if operation_mode.power_delivery_source_connected() {
+            Some(SourceKind::PowerDelivery)
+        } else if operation_mode.legacy_source_connected() {
+            Some(SourceKind::Legacy)
+        } else {
+            None
+        };
+        let attached = kind.map(|kind| {
+            (
+                kind,
+                Orientation::from(operation_mode.configuration_channel()),
+            )
+        });
+        let capabilities_changed =
+            self.last_status.new_power_data_object() && kind == Some(SourceKind::PowerDelivery);
+
+        let change = if attached == self.attached {
+            None
+        } else {
+            Some(match attached {
+                Some((kind, orientation)) => SourceEvent::Attached { kind, orientation },
+                None => SourceEvent::Detached,
+            })
+        };
+        self.attached = attached;
+
+        match (change, capabilities_changed) {
+            (Some(change), true) => {
+                self.pending = Some(SourceEvent::CapabilitiesChanged);
+                Ok(Some(change))
+            }
+            (Some(change), false) => Ok(Some(change)),
+            (None, true) => Ok(Some(SourceEvent::CapabilitiesChanged)),
+            (None, false) => Ok(None),
+        }
+    }
+}
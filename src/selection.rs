@@ -0,0 +1,135 @@
+//! # Autonomous PDO Selection
+//!
+//! The AP33772S negotiates with a source on its own as soon as one is attached, before the host
+//! sends any request. The rules it follows are spread across the datasheet and the EVB user guide:
+//!
+//! 1. During the first negotiation the AP33772S always requests the default PDO1 at 5V. A source
+//!    without USB Power Delivery is used at its fixed 5V in Non-PD Mode.
+//! 2. If EPR_MODE_EN is set in PDCONFIG and the source supports EPR, the AP33772S then tries to
+//!    enter EPR Mode. Entering EPR Mode only fills the SRC_EPR_PDOx registers and allows EPR
+//!    requests, the requested voltage is unchanged.
+//! 3. PPS_AVS_EN in PDCONFIG does not change the first request. It decides whether PPS and AVS
+//!    APDOs can be requested later and whether thermal de-rating can renegotiate the current.
+//! 4. Once a negotiation completes, the VOUT MOS switches are turned on only if the requested
+//!    voltage (VREQ) is greater than or equal to the Minimum Selection Voltage (VSELMIN). Otherwise
+//!    the output stays off and the LED shows MISMATCH.
+//!
+//! Every rail above 5V therefore needs an explicit request from the host. VSELMIN is the safety
+//! net for products that never send one: raising it above 5V keeps the output off until the host
+//! has negotiated a voltage the load can accept.
+//!
+//! [`predict_autonomous_selection`] applies these rules to a set of source capabilities and
+//! [`Ap33772s::configure_autonomous_selection`] writes the registers they depend on.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ap33772s_rs::Ap33772s;
+//! use ap33772s_rs::selection::predict_autonomous_selection;
+//! use ap33772s_rs::units::*;
+//!
+//! # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+//! let pdos = device.get_all_source_power_capabilities()?;
+//! let minimum_selection_voltage = device.get_minimum_selection_voltage()?;
+//! let power_delivery_mode = device.get_power_delivery_configuration()?;
+//!
+//! let selection =
+//!     predict_autonomous_selection(&pdos, minimum_selection_voltage, &power_delivery_mode)?;
+//! if !selection.output_enabled {
+//!     println!(
+//!         "VOUT stays off at {:.1} V until a higher voltage is requested",
+//!         selection.voltage.get::<volt>()
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Ap33772s::configure_autonomous_selection`]: crate::Ap33772s::configure_autonomous_selection
+use crate::errors::Ap33772sError;
+use crate::types::PowerDeliveryMode;
+use crate::types::command_structures::{AllSourceDataPowerDataObject, PowerDataObject};
+use crate::units::*;
+
+/// The voltage of a source without USB Power Delivery and of the default PDO1
+const DEFAULT_VOLTAGE: f32 = 5000.0; // mV
+
+/// The outcome of the AP33772S negotiating on its own, see [`predict_autonomous_selection`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutonomousSelection {
+    /// The PDO requested in the first negotiation, `None` for a source without Power Delivery
+    pub power_data_object: Option<PowerDataObject>,
+    /// The voltage that will be reported in VREQ
+    pub voltage: ElectricPotential,
+    /// Whether the VOUT MOS switches turn on, `voltage` is at least the Minimum Selection Voltage
+    pub output_enabled: bool,
+    /// Whether the AP33772S will try to enter EPR Mode after the first negotiation. It only
+    /// succeeds if the source supports EPR.
+    pub extended_power_range_entry: bool,
+}
+
+impl core::fmt::Display for AutonomousSelection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.power_data_object {
+            Some(power_data_object) => write!(f, "PDO{}", usize::from(power_data_object))?,
+            None => write!(f, "Non-PD")?,
+        }
+        write!(
+            f,
+            " {:.1} V, output {}",
+            self.voltage.get::<volt>(),
+            if self.output_enabled { "on" } else { "off" }
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for AutonomousSelection {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "{} {} V, output on: {}, EPR entry: {}",
+            self.power_data_object.map(usize::from),
+            self.voltage.get::<volt>(),
+            self.output_enabled,
+            self.extended_power_range_entry
+        );
+    }
+}
+
+/// Predicts what the AP33772S selects on attach when the host sends no request, following the
+/// rules in the [module documentation](self).
+///
+/// # Parameters
+///
+/// - `power_data_objects`: The source capabilities, an empty set is treated as a source without
+///   Power Delivery
+/// - `minimum_selection_voltage`: The Minimum Selection Voltage (VSELMIN)
+/// - `power_delivery_mode`: The PDCONFIG enables
+///
+/// # Returns
+///
+/// The predicted [`AutonomousSelection`], or [`Ap33772sError`] if PDO1 cannot be decoded.
+pub fn predict_autonomous_selection(
+    power_data_objects: &AllSourceDataPowerDataObject,
+    minimum_selection_voltage: ElectricPotential,
+    power_delivery_mode: &PowerDeliveryMode,
+) -> Result<AutonomousSelection, Ap33772sError> {
+    let default_power_data_object =
+        power_data_objects.get_power_data_object(PowerDataObject::StandardPowerRange1);
+    let power_delivery = default_power_data_object.is_detected();
+
+    let voltage = if power_delivery {
+        default_power_data_object.get_max_voltage()?
+    } else {
+        ElectricPotential::new::<millivolt>(DEFAULT_VOLTAGE)
+    };
+
+    Ok(AutonomousSelection {
+        power_data_object: power_delivery.then_some(PowerDataObject::StandardPowerRange1),
+        voltage,
+        output_enabled: voltage >= minimum_selection_voltage,
+        extended_power_range_entry: power_delivery
+            && power_delivery_mode.extended_power_range_mode_enabled,
+    })
+}
@@ -2,8 +2,10 @@
 //! setting the AP33772S in different states and modes
 use super::hal::*;
 use crate::ap33772s::Ap33772s;
+use crate::commands::command_map::Command;
 use crate::commands::configuration::power_delivery_configuration::PowerDeliveryConfiguration;
 use crate::commands::configuration::system_control::SystemControl;
+use crate::commands::misc::gpio::Gpio;
 use crate::commands::power_delivery::power_delivery_request_message::PowerDeliveryRequestMessage;
 use crate::commands::statistics::minimum_selection_voltage::MinimumSelectionVoltage;
 use crate::commands::thermal_resistances::convert_resistance_to_raw_resistance;
@@ -16,9 +18,9 @@ use crate::commands::thresholds::over_current_protection_threshold::OverCurrentP
 use crate::commands::thresholds::over_temperature_protection_threshold::OverTemperatureProtectionThreshold;
 use crate::commands::thresholds::over_voltage_protection_threshold::OverVoltageProtectionThreshold;
 use crate::commands::thresholds::under_voltage_protection_threshold::UnderVoltageProtectionThreshold;
+use crate::commands::thresholds::vdc_threshold::VDCTHR;
 use crate::errors::Ap33772sError;
 
-use crate::errors::RequestError;
 // Public API Types
 use crate::types::command_structures::*;
 use crate::types::*;
@@ -42,7 +44,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::{Ap33772s, types::command_structures::VoltageOutputControl};
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// // Enable output
     /// device.override_output_voltage(VoltageOutputControl::ForceOn)?;
     ///
@@ -57,7 +59,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     pub async fn override_output_voltage(
         &mut self,
         voltage_output: VoltageOutputControl,
-    ) -> Result<(), Ap33772sError> {
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
         let system_control: SystemControl = SystemControl::builder()
             .with_v_out_control(voltage_output)
             .build();
@@ -67,7 +69,10 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// Sets the minimum voltage that the device will negotiate during power delivery.
     ///
     /// This voltage represents the lowest voltage the device will accept during
-    /// USB-C Power Delivery negotiations.
+    /// USB-C Power Delivery negotiations. The VOUT MOS switches only turn on once the requested
+    /// voltage is at least this value, see [`selection`](crate::selection) for how it interacts
+    /// with the autonomous 5V request on attach. The voltage is rounded down to the 200mV
+    /// resolution of the register.
     ///
     /// # Parameters
     ///
@@ -81,7 +86,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::{Ap33772s, units::*};
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// // Set minimum selection voltage to 5V
     /// let min_voltage = ElectricPotential::new::<volt>(5.0);
     /// device.set_minimum_selection_voltage(min_voltage)?;
@@ -94,12 +99,13 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     pub async fn set_minimum_selection_voltage(
         &mut self,
         voltage: ElectricPotential,
-    ) -> Result<(), Ap33772sError> {
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
         let raw_voltage = MinimumSelectionVoltage::convert_voltage_to_raw_voltage(voltage)?;
         let minimum_selection_voltage = MinimumSelectionVoltage::builder()
             .with_raw_voltage(raw_voltage)
             .build();
-        self.write_one_byte_command(minimum_selection_voltage).await
+        self.write_one_byte_command_verified(minimum_selection_voltage)
+            .await
     }
 
     /// Configures advanced power delivery modes and capabilities.
@@ -120,7 +126,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::{Ap33772s, types::PowerDeliveryMode};
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// let mode = PowerDeliveryMode {
     ///     programmable_power_supply_adjustable_voltage_supply_enabled: true,
     ///     extended_power_range_mode_enabled: false,
@@ -136,19 +142,80 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     pub async fn set_power_delivery_mode(
         &mut self,
         mode: PowerDeliveryMode,
-    ) -> Result<(), Ap33772sError> {
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
         let command = PowerDeliveryConfiguration::builder()
             .with_extended_power_delivery_enabled(mode.extended_power_range_mode_enabled)
             .with_programmable_power_supply_and_adjustable_power_supply_enabled(
                 mode.programmable_power_supply_adjustable_voltage_supply_enabled,
             )
             .build();
-        self.write_one_byte_command(command).await
+        self.write_one_byte_command_verified(command).await
+    }
+
+    /// Configures what the AP33772S does on attach when the host never sends a request.
+    ///
+    /// Writes the Minimum Selection Voltage and the PDCONFIG enables. The first negotiation always
+    /// requests 5V, so a `minimum_voltage` above 5V keeps VOUT off until the host requests a high
+    /// enough voltage. See [`selection`] for the full rules and
+    /// [`predict_autonomous_selection`] to check the outcome against a source.
+    ///
+    /// The registers are only used for the first negotiation if they are written within 100ms of
+    /// STARTED being set in [`Status`], see [`Self::wait_until_ready`].
+    ///
+    /// # Parameters
+    ///
+    /// - `minimum_voltage`: The lowest voltage that may reach VOUT, rounded up to the 200mV
+    ///   resolution of VSELMIN so the output is never enabled below it
+    /// - `allow_programmable_power_supply`: Enables PPS and AVS requests (PPS_AVS_EN)
+    /// - `allow_extended_power_range`: Enables entering EPR Mode after the first negotiation
+    ///   (EPR_MODE_EN)
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or [`Ap33772sError`] on communication or conversion error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::{Ap33772s, units::*};
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Only connect the load once at least 9V has been negotiated
+    /// device.configure_autonomous_selection(ElectricPotential::new::<volt>(9.0), true, false)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`selection`]: crate::selection
+    /// [`predict_autonomous_selection`]: crate::selection::predict_autonomous_selection
+    #[maybe_async::maybe_async]
+    pub async fn configure_autonomous_selection(
+        &mut self,
+        minimum_voltage: ElectricPotential,
+        allow_programmable_power_supply: bool,
+        allow_extended_power_range: bool,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let raw_voltage = MinimumSelectionVoltage::convert_voltage_to_raw_voltage_rounded(
+            minimum_voltage,
+            Rounding::Up,
+        )?;
+        self.set_power_delivery_mode(PowerDeliveryMode {
+            programmable_power_supply_adjustable_voltage_supply_enabled:
+                allow_programmable_power_supply,
+            extended_power_range_mode_enabled: allow_extended_power_range,
+        })
+        .await?;
+        let minimum_selection_voltage = MinimumSelectionVoltage::builder()
+            .with_raw_voltage(raw_voltage)
+            .build();
+        self.write_one_byte_command_verified(minimum_selection_voltage)
+            .await
     }
 
     /// Send a Power Delivery Request directly to the AP33772S, this method does not check to see if the
-    /// request was applied. It does do some minor configuration checks to see if the requested message
-    /// is doable
+    /// request was applied. The request is checked against the source capabilities with
+    /// [`PowerRequest::validate`] and against the [`SafetyLimits`] before anything is written. The
+    /// current is capped to the [`CableRating`], if set.
     #[maybe_async::maybe_async]
     #[cfg_attr(feature = "advanced", visibility::make(pub))]
     pub(crate) async fn send_power_delivery_request(
@@ -158,68 +225,103 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
         voltage_selection: Option<ElectricPotential>,
         current_selection: OperatingCurrentSelection,
         data_objects: &AllSourceDataPowerDataObject,
-    ) -> Result<(), Ap33772sError> {
-        let data_object = data_objects.get_power_data_object(power_data_object_index);
-        // Check if the device can support the current draw
-        if data_object.get_max_current().max_range() < current_selection.current() {
-            return Err(Ap33772sError::InvalidRequest(
-                RequestError::CurrentOutOfRange,
-            ));
-        }
-        let delivery_message = if data_object.source_power_type() == PowerType::Fixed {
-            // If we are in fixed PDO Mode, the voltage selection is not needed.
-            PowerDeliveryRequestMessage::builder()
-                .with_voltage_selection(0)
-                .with_current_selection(current_selection)
-                .with_power_data_object_index(power_data_object_index)
-                .build()
-        } else {
-            let scaling_value = f32::from(data_object.voltage_resolution());
-            let voltage_selection = voltage_selection
-                .ok_or(Ap33772sError::InvalidRequest(RequestError::MissingArgument))?;
-            let scaled_voltage = voltage_selection.get::<millivolt>() / scaling_value;
-            // Check for overflow
-            let scaled_voltage = if scaled_voltage > f32::from(u8::MAX) {
-                Err(Ap33772sError::ConversionFailed)
-            } else {
-                Ok(scaled_voltage as u8)
-            }?;
-
-            if voltage_selection > data_object.get_max_voltage()? {
-                return Err(Ap33772sError::InvalidRequest(
-                    RequestError::VoltageOutOfRange,
-                ));
-            }
-            if voltage_selection < data_object.get_min_voltage()? {
-                return Err(Ap33772sError::InvalidRequest(
-                    RequestError::VoltageOutOfRange,
-                ));
-            }
-
-            PowerDeliveryRequestMessage::builder()
-                .with_voltage_selection(scaled_voltage)
-                .with_current_selection(current_selection)
-                .with_power_data_object_index(power_data_object_index)
-                .build()
-        };
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let delivery_message = self.checked_power_request(
+            power_data_object_index,
+            voltage_selection,
+            current_selection,
+            data_objects,
+        )?;
         self.write_two_byte_command(delivery_message).await
     }
 
     /// Will Attempt to get the maximum Power Output from the Power Data Object provided
     /// It does not check to see if this was set correctly
+    ///
+    /// When a [`CableRating`] or [`SafetyLimits`] are set the source capabilities are read first.
+    /// If the PDO offers more current than the cable is rated for, its maximum voltage is
+    /// requested at the rating instead, checked like [`Self::send_power_delivery_request`]. A PDO
+    /// above the safety limits is refused.
+    ///
+    /// Only available with the `advanced` feature, the driver negotiates with
+    /// [`Self::negotiate_maximum_power_delivery`].
     #[maybe_async::maybe_async]
-    #[cfg_attr(feature = "advanced", visibility::make(pub))]
-    pub(crate) async fn send_maximum_power_delivery_request(
+    #[cfg(feature = "advanced")]
+    pub async fn send_maximum_power_delivery_request(
         &mut self,
         power_data_object_index: PowerDataObject,
-    ) -> Result<(), Ap33772sError> {
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let delivery_message = self
+            .checked_maximum_power_request(power_data_object_index)
+            .await?;
+        self.write_two_byte_command(delivery_message).await
+    }
+
+    /// Builds and checks the request written by [`Self::send_maximum_power_delivery_request`]
+    #[maybe_async::maybe_async]
+    pub(crate) async fn checked_maximum_power_request(
+        &mut self,
+        power_data_object_index: PowerDataObject,
+    ) -> Result<PowerDeliveryRequestMessage, Ap33772sError<I2C::Error>> {
+        if self.cable_rating.is_some() || self.safety_limits.is_some() {
+            let data_objects = self.get_all_source_power_capabilities().await?;
+            let data_object = data_objects.get_power_data_object(power_data_object_index);
+            if self
+                .cable_rating
+                .is_some_and(|rating| rating.limits(data_object))
+            {
+                // The maximum voltage is only implied when CURRENT_SEL is also at its maximum
+                let voltage_selection = match data_object.source_power_type() {
+                    PowerType::Fixed => None,
+                    PowerType::Adjustable => Some(data_object.get_max_voltage()?),
+                };
+                return self.checked_power_request(
+                    power_data_object_index,
+                    voltage_selection,
+                    OperatingCurrentSelection::Maximum,
+                    &data_objects,
+                );
+            }
+            if let Some(limits) = self.safety_limits {
+                let current = data_object
+                    .get_max_current()
+                    .max_range()
+                    .min(OperatingCurrentSelection::Maximum.current());
+                limits.check(data_object, data_object.get_max_voltage()?, current)?;
+            }
+        }
         // Special message outlined in the AP33772S Datasheet Page 22
-        let delivery_message = PowerDeliveryRequestMessage::builder()
+        Ok(PowerDeliveryRequestMessage::builder()
             .with_voltage_selection(0xFF) // No Voltage Selection in Fixed Mode
             .with_current_selection(OperatingCurrentSelection::Maximum)
             .with_power_data_object_index(power_data_object_index)
+            .build())
+    }
+
+    /// Writes the VDCTHR register, the allowed difference between the requested and measured
+    /// output voltage, rounded down to whole percent.
+    ///
+    /// **Note**: This register is undocumented and the EVB user guide lists it as removed in
+    /// firmware 1.1, so the write may have no effect.
+    #[maybe_async::maybe_async]
+    pub async fn set_vdc_threshold(
+        &mut self,
+        threshold: Ratio,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let vdc_threshold = VDCTHR::builder()
+            .with_percentage(VDCTHR::convert_ratio_to_raw_percentage(threshold)?)
             .build();
-        self.write_two_byte_command(delivery_message).await
+        self.write_one_byte_command_verified(vdc_threshold).await
+    }
+
+    /// Writes the raw GPIO register.
+    ///
+    /// **Note**: No document describes the bits of this register, so the byte is written as
+    /// given.
+    #[maybe_async::maybe_async]
+    pub async fn set_gpio(&mut self, value: u8) -> Result<(), Ap33772sError<I2C::Error>> {
+        let gpio = Gpio::builder().with_value(value).build();
+        self.write_one_byte_command(gpio).await
     }
 }
 
@@ -241,7 +343,7 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// ```rust
     /// # use ap33772s_rs::{Ap33772s, types::ThermalResistances, units::*};
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// // Use default thermal resistances
     /// let resistances = ThermalResistances::default();
     /// device.set_thermal_resistances(resistances)?;
@@ -258,30 +360,17 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// # }
     /// ```
     ///
-    /// **Note**: This method performs four I2C writes.
+    /// **Note**: This method performs four I2C writes, followed by four reads when
+    /// [write verification](Self::set_write_verification) is enabled.
     ///
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
     #[maybe_async::maybe_async]
     pub async fn set_thermal_resistances(
         &mut self,
         resistances: ThermalResistances,
-    ) -> Result<(), Ap33772sError> {
-        let resistance_25 = ThermalResistance25::builder()
-            .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(resistances._25)?)
-            .build();
-        self.write_two_byte_command(resistance_25).await?;
-        let resistance_50 = ThermalResistance50::builder()
-            .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(resistances._50)?)
-            .build();
-        self.write_two_byte_command(resistance_50).await?;
-        let resistance_75 = ThermalResistance75::builder()
-            .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(resistances._75)?)
-            .build();
-        self.write_two_byte_command(resistance_75).await?;
-        let resistance_100 = ThermalResistance100::builder()
-            .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(resistances._100)?)
-            .build();
-        self.write_two_byte_command(resistance_100).await
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let registers = Self::thermal_resistance_registers(&resistances)?;
+        self.write_thermal_resistance_registers(registers).await
     }
 
     /// Sets all protection thresholds for the device.
@@ -295,13 +384,15 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     ///
     /// # Returns
     ///
-    /// `Ok(())` on success, or [`Ap33772sError`] on communication or conversion error.
+    /// `Ok(())` on success, [`Ap33772sError::SafetyLimitExceeded`] if a threshold is outside the
+    /// [`SafetyLimits`], see [`SafetyLimits::check_thresholds`], or [`Ap33772sError`] on
+    /// communication or conversion error.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use ap33772s_rs::{Ap33772s, types::{Thresholds, UnderVoltageThreshold}, units::*};
-    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
     /// let thresholds = Thresholds {
     ///     over_voltage: ElectricPotential::new::<volt>(22.0),
     ///     under_voltage: UnderVoltageThreshold::default(),
@@ -315,53 +406,621 @@ impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<
     /// # }
     /// ```
     ///
-    /// **Note**: This method performs multiple I2C writes.
+    /// **Note**: This method performs multiple I2C writes, each followed by a read when
+    /// [write verification](Self::set_write_verification) is enabled.
     ///
     /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`Ap33772sError::SafetyLimitExceeded`]: crate::errors::Ap33772sError::SafetyLimitExceeded
     #[maybe_async::maybe_async]
-    pub async fn set_thresholds(&mut self, thresholds: Thresholds) -> Result<(), Ap33772sError> {
-        let over_voltage_threshold: OverVoltageProtectionThreshold =
-            OverVoltageProtectionThreshold::builder()
+    pub async fn set_thresholds(
+        &mut self,
+        thresholds: Thresholds,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        let registers = self.threshold_registers(&thresholds)?;
+        self.write_threshold_registers(registers).await
+    }
+
+    /// Converts the thermal resistances to their TR registers
+    fn thermal_resistance_registers(
+        resistances: &ThermalResistances,
+    ) -> Result<ThermalResistanceRegisters, Ap33772sError<I2C::Error>> {
+        // All values are converted before any write so a conversion error never leaves the
+        // device partially configured
+        Ok(ThermalResistanceRegisters {
+            _25: ThermalResistance25::builder()
+                .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(
+                    resistances._25,
+                    Command::ThermalResistance25,
+                )?)
+                .build(),
+            _50: ThermalResistance50::builder()
+                .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(
+                    resistances._50,
+                    Command::ThermalResistance50,
+                )?)
+                .build(),
+            _75: ThermalResistance75::builder()
+                .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(
+                    resistances._75,
+                    Command::ThermalResistance75,
+                )?)
+                .build(),
+            _100: ThermalResistance100::builder()
+                .with_raw_thermal_resistance(convert_resistance_to_raw_resistance(
+                    resistances._100,
+                    Command::ThermalResistance100,
+                )?)
+                .build(),
+        })
+    }
+
+    #[maybe_async::maybe_async]
+    async fn write_thermal_resistance_registers(
+        &mut self,
+        registers: ThermalResistanceRegisters,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        self.write_two_byte_command_verified(registers._25).await?;
+        self.write_two_byte_command_verified(registers._50).await?;
+        self.write_two_byte_command_verified(registers._75).await?;
+        self.write_two_byte_command_verified(registers._100).await
+    }
+
+    /// Converts the thresholds to their registers, checking them against the [`SafetyLimits`]
+    fn threshold_registers(
+        &self,
+        thresholds: &Thresholds,
+    ) -> Result<ThresholdRegisters, Ap33772sError<I2C::Error>> {
+        // All values are converted before any write so a conversion error never leaves the
+        // device partially configured
+        let over_current = self.cap_over_current(thresholds.over_current);
+        if let Some(limits) = self.safety_limits {
+            limits.check_thresholds(&Thresholds {
+                over_current,
+                ..thresholds.clone()
+            })?;
+        }
+        Ok(ThresholdRegisters {
+            over_voltage: OverVoltageProtectionThreshold::builder()
                 .with_raw_voltage(
                     OverVoltageProtectionThreshold::convert_voltage_to_raw_voltage(
                         thresholds.over_voltage,
                     )?,
                 )
-                .build();
-        self.write_one_byte_command(over_voltage_threshold).await?;
-
-        let over_current_threshold: OverCurrentProtectionThreshold =
-            OverCurrentProtectionThreshold::builder()
+                .build(),
+            over_current: OverCurrentProtectionThreshold::builder()
                 .with_raw_current(
-                    OverCurrentProtectionThreshold::convert_current_to_raw_current(
-                        thresholds.over_current,
-                    )?,
+                    OverCurrentProtectionThreshold::convert_current_to_raw_current(over_current)?,
                 )
-                .build();
-        self.write_one_byte_command(over_current_threshold).await?;
-
-        let under_voltage_threshold: UnderVoltageProtectionThreshold =
-            UnderVoltageProtectionThreshold::builder()
+                .build(),
+            under_voltage: UnderVoltageProtectionThreshold::builder()
                 .with_threshold(thresholds.under_voltage)
-                .build();
-        self.write_one_byte_command(under_voltage_threshold).await?;
-
-        let over_temperature_threshold: OverTemperatureProtectionThreshold =
-            OverTemperatureProtectionThreshold::builder()
+                .build(),
+            over_temperature: OverTemperatureProtectionThreshold::builder()
                 .with_raw_temperature(
                     OverTemperatureProtectionThreshold::convert_temperature_to_raw_temperature(
                         thresholds.over_temperature,
                     )?,
                 )
-                .build();
-        self.write_one_byte_command(over_temperature_threshold)
+                .build(),
+            derating: DeRatingThreshold::builder()
+                .with_raw_temperature(DeRatingThreshold::convert_temperature_to_raw_temperature(
+                    thresholds.derating,
+                )?)
+                .build(),
+        })
+    }
+
+    #[maybe_async::maybe_async]
+    async fn write_threshold_registers(
+        &mut self,
+        registers: ThresholdRegisters,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        self.write_one_byte_command_verified(registers.over_voltage)
+            .await?;
+        self.write_one_byte_command_verified(registers.over_current)
+            .await?;
+        self.write_one_byte_command_verified(registers.under_voltage)
+            .await?;
+        self.write_one_byte_command_verified(registers.over_temperature)
             .await?;
+        self.write_one_byte_command_verified(registers.derating)
+            .await
+    }
+}
 
-        let derating_threshold: DeRatingThreshold = DeRatingThreshold::builder()
-            .with_raw_temperature(DeRatingThreshold::convert_temperature_to_raw_temperature(
-                thresholds.derating,
-            )?)
-            .build();
-        self.write_one_byte_command(derating_threshold).await
+/// The TR registers converted by [`Ap33772s::set_thermal_resistances`]
+struct ThermalResistanceRegisters {
+    _25: ThermalResistance25,
+    _50: ThermalResistance50,
+    _75: ThermalResistance75,
+    _100: ThermalResistance100,
+}
+
+/// The threshold registers converted by [`Ap33772s::set_thresholds`]
+struct ThresholdRegisters {
+    over_voltage: OverVoltageProtectionThreshold,
+    over_current: OverCurrentProtectionThreshold,
+    under_voltage: UnderVoltageProtectionThreshold,
+    over_temperature: OverTemperatureProtectionThreshold,
+    derating: DeRatingThreshold,
+}
+
+impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
+    /// The registers written by [`Self::set_thermal_resistances`], each two bytes wide
+    const THERMAL_RESISTANCE_REGISTERS: [Command; 4] = [
+        Command::ThermalResistance25,
+        Command::ThermalResistance50,
+        Command::ThermalResistance75,
+        Command::ThermalResistance100,
+    ];
+    /// The registers written by [`Self::set_thresholds`], each one byte wide
+    const THRESHOLD_REGISTERS: [Command; 5] = [
+        Command::OverVoltageProtectionThreshold,
+        Command::OverCurrentProtectionThreshold,
+        Command::UnderVoltageProtectionThreshold,
+        Command::OverTemperatureProtectionThreshold,
+        Command::DeRatingThreshold,
+    ];
+
+    /// Sets the thermal resistances as a single transaction.
+    ///
+    /// The resistances are converted as by [`Self::set_thermal_resistances`], then the four TR
+    /// registers are read and stored before they are written. If any write fails (including a
+    /// [write verification](Self::set_write_verification) mismatch), the stored values are written
+    /// back before the original error is returned, so the device is never left with a mix of old
+    /// and new resistances. A conversion error is returned before anything is read or written.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, the original [`Ap33772sError`] if the transaction was rolled back, or
+    /// [`Ap33772sError::RollbackFailed`] if the previous values could not be restored.
+    ///
+    /// **Note**: This method performs four additional I2C reads for the snapshot and up to four
+    /// additional writes if a rollback is required.
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`Ap33772sError::RollbackFailed`]: crate::errors::Ap33772sError::RollbackFailed
+    #[maybe_async::maybe_async]
+    pub async fn set_thermal_resistances_with_rollback(
+        &mut self,
+        resistances: ThermalResistances,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        // A locked register or unconvertible value is refused before any write, there is nothing
+        // to roll back
+        self.ensure_unlocked(Self::THERMAL_RESISTANCE_REGISTERS[0])?;
+        let registers = Self::thermal_resistance_registers(&resistances)?;
+        let snapshot = self
+            .snapshot_registers::<2, 4>(Self::THERMAL_RESISTANCE_REGISTERS)
+            .await?;
+        match self.write_thermal_resistance_registers(registers).await {
+            Ok(()) => Ok(()),
+            error => {
+                self.restore_registers(Self::THERMAL_RESISTANCE_REGISTERS, &snapshot, error)
+                    .await
+            }
+        }
+    }
+
+    /// Sets the protection thresholds as a single transaction.
+    ///
+    /// The thresholds are converted and checked as by [`Self::set_thresholds`], then the five
+    /// threshold registers are read and stored before they are written. If any write fails
+    /// (including a [write verification](Self::set_write_verification) mismatch), the stored values
+    /// are written back before the original error is returned. This prevents half-applied
+    /// protection settings such as a new OVP threshold combined with the previous OCP threshold.
+    /// A conversion error or [`Ap33772sError::SafetyLimitExceeded`] is returned before anything is
+    /// read or written.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, the original [`Ap33772sError`] if the transaction was rolled back, or
+    /// [`Ap33772sError::RollbackFailed`] if the previous values could not be restored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::{Ap33772s, types::Thresholds};
+    /// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+    /// device.set_write_verification(true);
+    /// device.set_thresholds_with_rollback(Thresholds::default())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// **Note**: This method performs five additional I2C reads for the snapshot and up to five
+    /// additional writes if a rollback is required.
+    ///
+    /// [`Ap33772sError`]: crate::errors::Ap33772sError
+    /// [`Ap33772sError::RollbackFailed`]: crate::errors::Ap33772sError::RollbackFailed
+    /// [`Ap33772sError::SafetyLimitExceeded`]: crate::errors::Ap33772sError::SafetyLimitExceeded
+    #[maybe_async::maybe_async]
+    pub async fn set_thresholds_with_rollback(
+        &mut self,
+        thresholds: Thresholds,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        // A locked register or a refused threshold is caught before any write, there is nothing
+        // to roll back
+        self.ensure_unlocked(Self::THRESHOLD_REGISTERS[0])?;
+        let registers = self.threshold_registers(&thresholds)?;
+        let snapshot = self
+            .snapshot_registers::<1, 5>(Self::THRESHOLD_REGISTERS)
+            .await?;
+        match self.write_threshold_registers(registers).await {
+            Ok(()) => Ok(()),
+            error => {
+                self.restore_registers(Self::THRESHOLD_REGISTERS, &snapshot, error)
+                    .await
+            }
+        }
+    }
+
+    /// Reads the raw contents of each register so they can be restored later
+    #[maybe_async::maybe_async]
+    async fn snapshot_registers<const WIDTH: usize, const N: usize>(
+        &mut self,
+        registers: [Command; N],
+    ) -> Result<[[u8; WIDTH]; N], Ap33772sError<I2C::Error>> {
+        let mut snapshot = [[0x00; WIDTH]; N];
+        for (register, value) in registers.into_iter().zip(snapshot.iter_mut()) {
+            self.read_raw_register(register, value).await?;
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes a snapshot taken by [`Self::snapshot_registers`] back to the device after a failed
+    /// transaction and returns `outcome`, the error or rejected request that started the rollback.
+    /// Every register is restored, as a failed write may still have been applied by the device.
+    #[maybe_async::maybe_async]
+    async fn restore_registers<T, const WIDTH: usize, const N: usize>(
+        &mut self,
+        registers: [Command; N],
+        snapshot: &[[u8; WIDTH]; N],
+        outcome: Result<T, Ap33772sError<I2C::Error>>,
+    ) -> Result<T, Ap33772sError<I2C::Error>> {
+        for (register, value) in registers.into_iter().zip(snapshot.iter()) {
+            if let Err(rollback) = self.write_raw_register(register, value).await {
+                return Err(Ap33772sError::rollback_failed(
+                    outcome.err(),
+                    register,
+                    rollback,
+                ));
+            }
+        }
+        outcome
+    }
+}
+
+/// These methods keep the over voltage and over current thresholds in step with the negotiated
+/// contract when [`ThresholdTracking`] is enabled
+impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
+    /// The registers rewritten by threshold tracking, in the order of [`ThresholdTracking::raw_thresholds`]
+    const TRACKED_THRESHOLD_REGISTERS: [Command; 2] = [
+        Command::OverVoltageProtectionThreshold,
+        Command::OverCurrentProtectionThreshold,
+    ];
+
+    /// Loosens the tracked thresholds ahead of a contract change so neither protection trips during
+    /// the transition. Returns the previous register values so they can be restored if the
+    /// negotiation fails.
+    #[maybe_async::maybe_async]
+    pub(crate) async fn loosen_tracked_thresholds(
+        &mut self,
+        tracking: ThresholdTracking,
+        voltage: ElectricPotential,
+        current: ElectricCurrent,
+    ) -> Result<[[u8; 1]; 2], Ap33772sError<I2C::Error>> {
+        // Refused before the request is sent, as the thresholds could not be tightened afterwards
+        self.ensure_unlocked(Self::TRACKED_THRESHOLD_REGISTERS[0])?;
+        let [target_over_voltage, target_over_current] =
+            self.tracked_thresholds(tracking, voltage, current)?;
+        let previous = self
+            .snapshot_registers::<1, 2>(Self::TRACKED_THRESHOLD_REGISTERS)
+            .await?;
+        let [[previous_over_voltage], [previous_over_current]] = previous;
+        // An OCPTHR of zero selects 110% of the PDO maximum current, which is already the loosest
+        // setting unless a cable rating or safety limit caps it
+        let over_current = if previous_over_current == 0 && !self.replaces_unset_over_current() {
+            0
+        } else {
+            previous_over_current.max(target_over_current)
+        };
+        let loosened = [previous_over_voltage.max(target_over_voltage), over_current];
+        if loosened != [previous_over_voltage, previous_over_current] {
+            self.write_tracked_thresholds(loosened).await?;
+        }
+        Ok(previous)
+    }
+
+    /// Completes threshold tracking once a negotiation has finished. An accepted contract is read
+    /// back and the thresholds are tightened to it, a rejected or failed negotiation restores the
+    /// previous thresholds. While the device is still busy the loosened thresholds are kept, as
+    /// the new contract may still be applied. They are also kept if the thresholds for the
+    /// accepted contract exceed the [`SafetyLimits`].
+    #[maybe_async::maybe_async]
+    pub(crate) async fn finish_threshold_tracking(
+        &mut self,
+        tracking: ThresholdTracking,
+        previous: &[[u8; 1]; 2],
+        response: Result<PowerDeliveryResponse, Ap33772sError<I2C::Error>>,
+    ) -> Result<PowerDeliveryResponse, Ap33772sError<I2C::Error>> {
+        match response {
+            Ok(PowerDeliveryResponse::Success) => {
+                let voltage = self.get_requested_voltage().await?;
+                let current = self.get_requested_current().await?;
+                let current = self.cap_over_current(current);
+                let thresholds = self.tracked_thresholds(tracking, voltage, current)?;
+                self.write_tracked_thresholds(thresholds).await?;
+                Ok(PowerDeliveryResponse::Success)
+            }
+            Ok(PowerDeliveryResponse::Busy) => Ok(PowerDeliveryResponse::Busy),
+            response => {
+                self.restore_registers(Self::TRACKED_THRESHOLD_REGISTERS, previous, response)
+                    .await
+            }
+        }
+    }
+
+    /// Returns the raw OVPTHR and OCPTHR values tracking a contract, checked against the
+    /// [`SafetyLimits`] like [`Self::set_thresholds`]
+    fn tracked_thresholds(
+        &self,
+        tracking: ThresholdTracking,
+        voltage: ElectricPotential,
+        current: ElectricCurrent,
+    ) -> Result<[u8; 2], Ap33772sError<I2C::Error>> {
+        if let Some(limits) = self.safety_limits {
+            limits.check_over_voltage(tracking.over_voltage(voltage))?;
+            limits.check_current(current)?;
+        }
+        Ok(tracking.raw_thresholds(voltage, current)?)
+    }
+
+    #[maybe_async::maybe_async]
+    async fn write_tracked_thresholds(
+        &mut self,
+        [over_voltage, over_current]: [u8; 2],
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        self.write_one_byte_command_verified(
+            OverVoltageProtectionThreshold::builder()
+                .with_raw_voltage(over_voltage)
+                .build(),
+        )
+        .await?;
+        self.write_one_byte_command_verified(
+            OverCurrentProtectionThreshold::builder()
+                .with_raw_current(over_current)
+                .build(),
+        )
+        .await
+    }
+}
+
+/// These methods cap current requests and the over current threshold to the [`CableRating`] and
+/// check them against the [`SafetyLimits`]
+impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
+    /// Caps the current to the cable rating, validates the request against the source
+    /// capabilities and checks it against the safety limits
+    pub(crate) fn checked_power_request(
+        &self,
+        power_data_object_index: PowerDataObject,
+        voltage_selection: Option<ElectricPotential>,
+        current_selection: OperatingCurrentSelection,
+        data_objects: &AllSourceDataPowerDataObject,
+    ) -> Result<PowerDeliveryRequestMessage, Ap33772sError<I2C::Error>> {
+        let current_selection = self.cap_current_selection(current_selection);
+        let delivery_message = PowerRequest::new(
+            power_data_object_index,
+            voltage_selection,
+            current_selection,
+        )
+        .validate(data_objects)?;
+        if let Some(limits) = self.safety_limits {
+            let data_object = data_objects.get_power_data_object(power_data_object_index);
+            let voltage = match voltage_selection {
+                Some(voltage) if data_object.source_power_type() != PowerType::Fixed => voltage,
+                _ => data_object.get_max_voltage()?,
+            };
+            limits.check(data_object, voltage, current_selection.current())?;
+        }
+        Ok(delivery_message)
+    }
+
+    /// Lowers a current selection above the cable rating to the rating
+    pub(crate) fn cap_current_selection(
+        &self,
+        selection: OperatingCurrentSelection,
+    ) -> OperatingCurrentSelection {
+        match self.cable_rating {
+            Some(rating) => rating.cap(selection),
+            None => selection,
+        }
+    }
+
+    /// Lowers an over current threshold above the cable rating to the rating. Zero, which selects
+    /// 110% of the PDO maximum current, is replaced by the lower of the cable rating and the
+    /// current limit of the safety limits.
+    pub(crate) fn cap_over_current(&self, current: ElectricCurrent) -> ElectricCurrent {
+        if current.get::<milliampere>() == 0.0 {
+            return self.over_current_ceiling().unwrap_or(current);
+        }
+        match self.cable_rating {
+            Some(rating) if current > rating.maximum_current() => rating.maximum_current(),
+            _ => current,
+        }
+    }
+
+    /// Returns `true` if [`Self::cap_over_current`] replaces an OCPTHR of zero
+    fn replaces_unset_over_current(&self) -> bool {
+        self.over_current_ceiling().is_some()
+    }
+
+    /// Returns the lower of the cable rating and the current limit, `None` if neither is set
+    fn over_current_ceiling(&self) -> Option<ElectricCurrent> {
+        let cable_rating = self.cable_rating.map(|rating| rating.maximum_current());
+        let limit = self
+            .safety_limits
+            .map(|limits| limits.maximum_current)
+            .filter(|current| current.is_finite());
+        match (cable_rating, limit) {
+            (Some(cable_rating), Some(limit)) => Some(cable_rating.min(limit)),
+            (cable_rating, limit) => cable_rating.or(limit),
+        }
+    }
+}
+
+/// The register values frozen by [`Ap33772s::lock_configuration`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LockedConfiguration {
+    one_byte_registers: [[u8; 1]; 7],
+    thermal_resistances: [[u8; 2]; 4],
+}
+
+/// These methods freeze the safety relevant registers once the device has been configured
+impl<I2C: I2c, D: DelayNs, #[cfg(feature = "interrupts")] P: InputPin> Ap33772s<I2C, D> {
+    /// The one byte registers frozen by [`Self::lock_configuration`], the two byte thermal
+    /// resistances are in [`Self::THERMAL_RESISTANCE_REGISTERS`]
+    const LOCKED_REGISTERS: [Command; 7] = [
+        Command::SystemConfiguration,
+        Command::PowerDeliveryConfiguration,
+        Command::OverVoltageProtectionThreshold,
+        Command::OverCurrentProtectionThreshold,
+        Command::UnderVoltageProtectionThreshold,
+        Command::OverTemperatureProtectionThreshold,
+        Command::DeRatingThreshold,
+    ];
+
+    /// Locks the protection thresholds, CONFIG, PDCONFIG and the thermal resistances.
+    ///
+    /// The registers are read and their values kept. Until
+    /// [`Self::unlock_configuration_allowing_protection_changes`] is called, every write to them
+    /// returns [`Ap33772sError::ConfigurationLocked`] without communicating with the device. This
+    /// covers [`Self::set_thresholds`], [`Self::set_thermal_resistances`],
+    /// [`Self::set_power_delivery_mode`] and, with the `advanced` feature, raw writes of the same
+    /// [`Command`]s. [`ThresholdTracking`] rewrites the thresholds, so negotiating with tracking
+    /// enabled is refused as well.
+    ///
+    /// Call [`Self::verify_locked_configuration`] periodically to check that the device still
+    /// holds the locked values. Locking an already locked configuration keeps the original values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::Ap33772s;
+    /// # fn example(i2c: impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, delay: impl embedded_hal::delay::DelayNs) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut device = Ap33772s::new_default(i2c, delay)?;
+    /// device.lock_configuration()?;
+    /// loop {
+    ///     device.verify_locked_configuration()?;
+    ///     // Sleep until the next check
+    /// #   break;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ap33772sError::ConfigurationLocked`]: crate::errors::Ap33772sError::ConfigurationLocked
+    #[maybe_async::maybe_async]
+    pub async fn lock_configuration(&mut self) -> Result<(), Ap33772sError<I2C::Error>> {
+        if self.locked_configuration.is_some() {
+            return Ok(());
+        }
+        let one_byte_registers = self
+            .snapshot_registers::<1, 7>(Self::LOCKED_REGISTERS)
+            .await?;
+        let thermal_resistances = self
+            .snapshot_registers::<2, 4>(Self::THERMAL_RESISTANCE_REGISTERS)
+            .await?;
+        self.locked_configuration = Some(LockedConfiguration {
+            one_byte_registers,
+            thermal_resistances,
+        });
+        Ok(())
+    }
+
+    /// Unlocks the configuration locked by [`Self::lock_configuration`], allowing the protection
+    /// thresholds, CONFIG, PDCONFIG and thermal resistances to be written again.
+    pub fn unlock_configuration_allowing_protection_changes(&mut self) {
+        self.locked_configuration = None;
+    }
+
+    /// Returns `true` if the configuration is locked. See [`Self::lock_configuration`].
+    pub fn is_configuration_locked(&self) -> bool {
+        self.locked_configuration.is_some()
+    }
+
+    /// Reads the locked registers back and checks that they still hold the values they had when
+    /// the configuration was locked. Does nothing if the configuration is not locked.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every register matches, otherwise
+    /// [`Ap33772sError::LockedConfigurationChanged`] for the first register that differs.
+    ///
+    /// **Note**: This method performs eleven I2C reads.
+    ///
+    /// [`Ap33772sError::LockedConfigurationChanged`]: crate::errors::Ap33772sError::LockedConfigurationChanged
+    #[maybe_async::maybe_async]
+    pub async fn verify_locked_configuration(&mut self) -> Result<(), Ap33772sError<I2C::Error>> {
+        let Some(locked) = self.locked_configuration else {
+            return Ok(());
+        };
+        let one_byte_registers = self
+            .snapshot_registers::<1, 7>(Self::LOCKED_REGISTERS)
+            .await?;
+        let thermal_resistances = self
+            .snapshot_registers::<2, 4>(Self::THERMAL_RESISTANCE_REGISTERS)
+            .await?;
+
+        let one_byte = Self::LOCKED_REGISTERS
+            .into_iter()
+            .zip(
+                locked
+                    .one_byte_registers
+                    .iter()
+                    .zip(one_byte_registers.iter()),
+            )
+            .map(|(command, (expected, actual))| {
+                (command, u16::from(expected[0]), u16::from(actual[0]))
+            });
+        let two_byte = Self::THERMAL_RESISTANCE_REGISTERS
+            .into_iter()
+            .zip(
+                locked
+                    .thermal_resistances
+                    .iter()
+                    .zip(thermal_resistances.iter()),
+            )
+            .map(|(command, (expected, actual))| {
+                (
+                    command,
+                    u16::from_le_bytes(*expected),
+                    u16::from_le_bytes(*actual),
+                )
+            });
+        match one_byte
+            .chain(two_byte)
+            .find(|(_, expected, actual)| expected != actual)
+        {
+            Some((command, expected, actual)) => Err(Ap33772sError::LockedConfigurationChanged {
+                command,
+                expected,
+                actual,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuses a write to a register frozen by [`Self::lock_configuration`]
+    pub(crate) fn ensure_unlocked(
+        &self,
+        command: Command,
+    ) -> Result<(), Ap33772sError<I2C::Error>> {
+        if self.locked_configuration.is_some()
+            && (Self::LOCKED_REGISTERS.contains(&command)
+                || Self::THERMAL_RESISTANCE_REGISTERS.contains(&command))
+        {
+            return Err(Ap33772sError::ConfigurationLocked(command));
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,372 @@
+//! # NTC Thermistor Models
+//!
+//! The AP33772S converts the resistance of the NTC thermistor connected to its OTP pin into a
+//! temperature using four resistance values written to the TR25, TR50, TR75 and TR100 registers
+//! (see [`ThermalResistances`]). This module derives those four values from the characteristic
+//! data published by thermistor manufacturers, so they do not need to be looked up by hand.
+//!
+//! Two models are supported:
+//!
+//! - **Beta (β) model**: `R(T) = R25 · e^(β · (1/T − 1/T25))`, using the resistance at 25°C and the
+//!   β value from the thermistor datasheet
+//! - **Steinhart–Hart equation**: `1/T = A + B · ln(R) + C · ln(R)³`, using the three coefficients
+//!   from the datasheet or fitted from a resistance/temperature table
+//!
+//! A catalogue of common thermistors is provided through [`Thermistor`].
+//!
+//! [`ThermalModel`] works in the other direction: it reproduces the chip's own conversion from the
+//! four configured resistances, so temperatures can be calculated on the host and the accuracy of
+//! the chip's approximation can be estimated against a real thermistor.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use ap33772s_rs::thermistor::{Thermistor, ThermistorModel};
+//! use ap33772s_rs::types::ThermalResistances;
+//! use ap33772s_rs::units::*;
+//!
+//! // From a part in the catalogue
+//! let resistances = ThermalResistances::from_thermistor(Thermistor::MurataNcp15xh103f03rc)?;
+//!
+//! // From the values on a thermistor datasheet
+//! let resistances = ThermalResistances::from_beta(ElectricalResistance::new::<ohm>(10_000.0), 3435.0)?;
+//!
+//! // Or evaluate the model directly
+//! let model = ThermistorModel::Beta {
+//!     r25: ElectricalResistance::new::<ohm>(10_000.0),
+//!     beta: 3435.0,
+//! };
+//! let resistance = model.resistance_at(ThermodynamicTemperature::new::<degree_celsius>(60.0))?;
+//! # Ok::<(), ap33772s_rs::types::Ap33772sError>(())
+//! ```
+//!
+//! [`ThermalResistances`]: crate::types::ThermalResistances
+use crate::commands::command_map::Command;
+use crate::errors::Ap33772sError;
+use crate::types::ThermalResistances;
+use crate::units::*;
+
+/// The offset between degrees Celsius and Kelvin
+const ZERO_CELSIUS_IN_KELVIN: f64 = 273.15;
+/// The reference temperature of the Beta model in degrees Celsius
+const REFERENCE_TEMPERATURE: f64 = 25.0;
+
+/// A mathematical model describing how the resistance of an NTC thermistor changes with temperature.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThermistorModel {
+    /// The Beta (β) model, defined by the resistance at 25°C and the β value in Kelvin.
+    /// Manufacturers usually quote β between two temperatures, e.g. B25/50 or B25/85; the model is most
+    /// accurate between those two points.
+    Beta {
+        /// The nominal resistance at 25°C
+        r25: ElectricalResistance,
+        /// The β value in Kelvin
+        beta: f32,
+    },
+    /// The Steinhart–Hart equation `1/T = A + B · ln(R) + C · ln(R)³` with `T` in Kelvin and `R` in ohms.
+    SteinhartHart {
+        /// The `A` coefficient
+        a: f32,
+        /// The `B` coefficient
+        b: f32,
+        /// The `C` coefficient
+        c: f32,
+    },
+}
+
+impl ThermistorModel {
+    /// Returns the resistance of the thermistor at the given temperature.
+    ///
+    /// Returns [`Ap33772sError::ConversionFailed`] if the model parameters or the temperature do not
+    /// produce a finite, positive resistance.
+    pub fn resistance_at(
+        &self,
+        temperature: ThermodynamicTemperature,
+    ) -> Result<ElectricalResistance, Ap33772sError> {
+        let kelvins = f64::from(temperature.get::<degree_celsius>()) + ZERO_CELSIUS_IN_KELVIN;
+        if !kelvins.is_finite() || kelvins <= 0.0 {
+            return Err(Ap33772sError::ConversionFailed {
+                command: None,
+                value: temperature.get::<degree_celsius>(),
+            });
+        }
+        let ohms = match self {
+            ThermistorModel::Beta { r25, beta } => {
+                let r25 = f64::from(r25.get::<ohm>());
+                let beta = f64::from(*beta);
+                let reference = REFERENCE_TEMPERATURE + ZERO_CELSIUS_IN_KELVIN;
+                r25 * libm::exp(beta * (1.0 / kelvins - 1.0 / reference))
+            }
+            ThermistorModel::SteinhartHart { a, b, c } => {
+                // Solving the cubic in ln(R), see the Steinhart–Hart inverse equation
+                let (a, b, c) = (f64::from(*a), f64::from(*b), f64::from(*c));
+                let x = (a - 1.0 / kelvins) / c;
+                let y = libm::sqrt(libm::pow(b / (3.0 * c), 3.0) + x * x / 4.0);
+                libm::exp(libm::cbrt(y - x / 2.0) - libm::cbrt(y + x / 2.0))
+            }
+        };
+        if !ohms.is_finite() || ohms <= 0.0 {
+            return Err(Ap33772sError::ConversionFailed {
+                command: None,
+                value: ohms as f32,
+            });
+        }
+        Ok(ElectricalResistance::new::<ohm>(ohms as f32))
+    }
+
+    /// Returns the temperature of the thermistor at the given resistance.
+    ///
+    /// Returns [`Ap33772sError::ConversionFailed`] if the resistance is not finite and positive or the
+    /// model parameters do not produce a temperature above absolute zero.
+    pub fn temperature_at(
+        &self,
+        resistance: ElectricalResistance,
+    ) -> Result<ThermodynamicTemperature, Ap33772sError> {
+        let ohms = f64::from(resistance.get::<ohm>());
+        if !ohms.is_finite() || ohms <= 0.0 {
+            return Err(Ap33772sError::ConversionFailed {
+                command: None,
+                value: ohms as f32,
+            });
+        }
+        let inverse_kelvin = match self {
+            ThermistorModel::Beta { r25, beta } => {
+                let r25 = f64::from(r25.get::<ohm>());
+                let reference = REFERENCE_TEMPERATURE + ZERO_CELSIUS_IN_KELVIN;
+                1.0 / reference + libm::log(ohms / r25) / f64::from(*beta)
+            }
+            ThermistorModel::SteinhartHart { a, b, c } => {
+                let ln_r = libm::log(ohms);
+                f64::from(*a) + f64::from(*b) * ln_r + f64::from(*c) * ln_r * ln_r * ln_r
+            }
+        };
+        if !inverse_kelvin.is_finite() || inverse_kelvin <= 0.0 {
+            return Err(Ap33772sError::ConversionFailed {
+                command: None,
+                value: ohms as f32,
+            });
+        }
+        let celsius = 1.0 / inverse_kelvin - ZERO_CELSIUS_IN_KELVIN;
+        Ok(ThermodynamicTemperature::new::<degree_celsius>(
+            celsius as f32,
+        ))
+    }
+}
+
+/// A catalogue of commonly used NTC thermistors.
+///
+/// Each part is described by the Beta model using the nominal resistance and β value from the
+/// manufacturer's datasheet. Only parts whose resistance at 25°C fits in the 16-bit TR registers
+/// (at most 65535 Ω) are listed, as higher value thermistors cannot be configured on the AP33772S.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Thermistor {
+    /// Murata NCP15XH103F03RC, 10 kΩ, B25/85 = 3435 K. The AP33772S register defaults are
+    /// taken from this part's resistance table.
+    MurataNcp15xh103f03rc,
+    /// Murata NCP15WB473F03RC, 47 kΩ, B25/50 = 4050 K
+    MurataNcp15wb473f03rc,
+    /// Semitec 103AT-2, 10 kΩ, B25/85 = 3435 K
+    Semitec103At2,
+    /// TDK (EPCOS) B57861S0103F040, 10 kΩ, B25/100 = 3988 K
+    TdkB57861s0103f040,
+    /// Vishay NTCLE100E3103JB0, 10 kΩ, B25/85 = 3977 K
+    VishayNtcle100e3103jb0,
+}
+
+impl Thermistor {
+    /// Returns the Beta model for this thermistor.
+    pub fn model(&self) -> ThermistorModel {
+        let (r25, beta) = match self {
+            Thermistor::MurataNcp15xh103f03rc => (10_000.0, 3435.0),
+            Thermistor::MurataNcp15wb473f03rc => (47_000.0, 4050.0),
+            Thermistor::Semitec103At2 => (10_000.0, 3435.0),
+            Thermistor::TdkB57861s0103f040 => (10_000.0, 3988.0),
+            Thermistor::VishayNtcle100e3103jb0 => (10_000.0, 3977.0),
+        };
+        ThermistorModel::Beta {
+            r25: ElectricalResistance::new::<ohm>(r25),
+            beta,
+        }
+    }
+}
+
+impl core::fmt::Display for Thermistor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Thermistor::MurataNcp15xh103f03rc => "Murata NCP15XH103F03RC",
+            Thermistor::MurataNcp15wb473f03rc => "Murata NCP15WB473F03RC",
+            Thermistor::Semitec103At2 => "Semitec 103AT-2",
+            Thermistor::TdkB57861s0103f040 => "TDK B57861S0103F040",
+            Thermistor::VishayNtcle100e3103jb0 => "Vishay NTCLE100E3103JB0",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The temperatures in degrees Celsius of the four TR registers
+const TABLE_TEMPERATURES: [f32; 4] = [25.0, 50.0, 75.0, 100.0];
+/// The four TR registers, ordered as [`TABLE_TEMPERATURES`]
+const TABLE_REGISTERS: [Command; 4] = [
+    Command::ThermalResistance25,
+    Command::ThermalResistance50,
+    Command::ThermalResistance75,
+    Command::ThermalResistance100,
+];
+/// The spacing in degrees Celsius between the samples taken by [`ThermalModel::error_band`]
+const ERROR_BAND_STEP: f32 = 0.5;
+
+/// A host-side model of the AP33772S resistance to temperature conversion.
+///
+/// The chip converts the NTC resistance into a temperature using only the four points of the
+/// configured [`ThermalResistances`]. The datasheet does not describe the conversion, so this model
+/// assumes linear interpolation in resistance between adjacent points, extended along the first and
+/// last segments outside 25°C to 100°C.
+///
+/// # Examples
+///
+/// ```rust
+/// use ap33772s_rs::thermistor::{Thermistor, ThermalModel};
+/// use ap33772s_rs::types::ThermalResistances;
+/// use ap33772s_rs::units::*;
+///
+/// let model = ThermalModel::new(&ThermalResistances::default())?;
+/// let temperature = model.temperature_at(ElectricalResistance::new::<ohm>(1928.0));
+/// assert!((temperature.get::<degree_celsius>() - 75.0).abs() < 0.01);
+///
+/// // How far the chip's reading can drift from the real thermistor temperature
+/// let band = model.error_band(
+///     &Thermistor::MurataNcp15xh103f03rc.model(),
+///     ThermodynamicTemperature::new::<degree_celsius>(25.0),
+///     ThermodynamicTemperature::new::<degree_celsius>(100.0),
+/// )?;
+/// # Ok::<(), ap33772s_rs::types::Ap33772sError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalModel {
+    /// The configured resistances in ohms, ordered as [`TABLE_TEMPERATURES`]
+    resistances: [f32; 4],
+}
+
+/// The deviation between the temperature calculated by [`ThermalModel`] and the real temperature
+/// of a thermistor over a range, as returned by [`ThermalModel::error_band`].
+///
+/// A positive error means the chip reports a higher temperature than the thermistor is at. The band
+/// does not include the 1°C resolution of the TEMP register.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorBand {
+    /// The most negative error found in the range
+    pub lowest: TemperatureInterval,
+    /// The most positive error found in the range
+    pub highest: TemperatureInterval,
+    /// The thermistor temperature at which the largest absolute error was found
+    pub worst_case_at: ThermodynamicTemperature,
+}
+
+impl ThermalModel {
+    /// Creates a model of the chip's conversion from the configured thermal resistances.
+    ///
+    /// Returns [`Ap33772sError::ConversionFailed`] naming the first TR register whose resistance is not
+    /// finite, positive and lower than the one before it, as required for an NTC thermistor.
+    pub fn new(resistances: &ThermalResistances) -> Result<Self, Ap33772sError> {
+        let resistances = [
+            resistances._25.get::<ohm>(),
+            resistances._50.get::<ohm>(),
+            resistances._75.get::<ohm>(),
+            resistances._100.get::<ohm>(),
+        ];
+        let mut previous = f32::INFINITY;
+        for (resistance, command) in resistances.iter().zip(TABLE_REGISTERS) {
+            if !resistance.is_finite() || *resistance <= 0.0 || *resistance >= previous {
+                return Err(Ap33772sError::conversion(command, *resistance));
+            }
+            previous = *resistance;
+        }
+        Ok(ThermalModel { resistances })
+    }
+
+    /// Returns the temperature the chip calculates for the given thermistor resistance.
+    pub fn temperature_at(&self, resistance: ElectricalResistance) -> ThermodynamicTemperature {
+        let ohms = resistance.get::<ohm>();
+        // Resistances decrease with temperature, so find the first point below the reading
+        let segment = self
+            .resistances
+            .iter()
+            .skip(1)
+            .position(|r| ohms >= *r)
+            .unwrap_or(self.resistances.len() - 2);
+        let (r_low, r_high) = (self.resistances[segment], self.resistances[segment + 1]);
+        let (t_low, t_high) = (TABLE_TEMPERATURES[segment], TABLE_TEMPERATURES[segment + 1]);
+        let celsius = t_low + (r_low - ohms) * (t_high - t_low) / (r_low - r_high);
+        ThermodynamicTemperature::new::<degree_celsius>(celsius)
+    }
+
+    /// Returns the thermistor resistance at which the chip calculates the given temperature.
+    ///
+    /// Returns [`Ap33772sError::ConversionFailed`] naming the TR register the table is extended
+    /// along if extending it to the temperature does not produce a positive resistance.
+    pub fn resistance_at(
+        &self,
+        temperature: ThermodynamicTemperature,
+    ) -> Result<ElectricalResistance, Ap33772sError> {
+        let celsius = temperature.get::<degree_celsius>();
+        let segment = TABLE_TEMPERATURES
+            .iter()
+            .skip(1)
+            .position(|t| celsius <= *t)
+            .unwrap_or(TABLE_TEMPERATURES.len() - 2);
+        let (r_low, r_high) = (self.resistances[segment], self.resistances[segment + 1]);
+        let (t_low, t_high) = (TABLE_TEMPERATURES[segment], TABLE_TEMPERATURES[segment + 1]);
+        let ohms = r_low - (celsius - t_low) * (r_low - r_high) / (t_high - t_low);
+        if !ohms.is_finite() || ohms <= 0.0 {
+            return Err(Ap33772sError::conversion(
+                TABLE_REGISTERS[segment + 1],
+                celsius,
+            ));
+        }
+        Ok(ElectricalResistance::new::<ohm>(ohms))
+    }
+
+    /// Estimates how far the chip's calculated temperature deviates from the real temperature of
+    /// the given thermistor between `from` and `to`, sampling every 0.5°C.
+    ///
+    /// Returns [`Ap33772sError::ConversionFailed`] if `from` is above `to` or the thermistor model
+    /// cannot be evaluated over the range.
+    pub fn error_band(
+        &self,
+        thermistor: &ThermistorModel,
+        from: ThermodynamicTemperature,
+        to: ThermodynamicTemperature,
+    ) -> Result<ErrorBand, Ap33772sError> {
+        let (from, to) = (from.get::<degree_celsius>(), to.get::<degree_celsius>());
+        if !from.is_finite() || !to.is_finite() || from > to {
+            return Err(Ap33772sError::ConversionFailed {
+                command: None,
+                value: to,
+            });
+        }
+        let (mut lowest, mut highest, mut worst_case_at) = (f32::MAX, f32::MIN, from);
+        let mut step = 0;
+        loop {
+            let celsius = (from + step as f32 * ERROR_BAND_STEP).min(to);
+            let actual = ThermodynamicTemperature::new::<degree_celsius>(celsius);
+            let calculated = self.temperature_at(thermistor.resistance_at(actual)?);
+            let error = calculated.get::<degree_celsius>() - celsius;
+            if error.abs() > lowest.abs().max(highest.abs()) || step == 0 {
+                worst_case_at = celsius;
+            }
+            lowest = lowest.min(error);
+            highest = highest.max(error);
+            if celsius >= to {
+                break;
+            }
+            step += 1;
+        }
+        Ok(ErrorBand {
+            lowest: TemperatureInterval::new::<kelvin>(lowest),
+            highest: TemperatureInterval::new::<kelvin>(highest),
+            worst_case_at: ThermodynamicTemperature::new::<degree_celsius>(worst_case_at),
+        })
+    }
+}
@@ -14,11 +14,14 @@
 /// - Direct register manipulation capabilities
 /// - Low-level device configuration options
 pub mod command_structures {
+    pub use crate::commands::command_map::Command;
     pub use crate::commands::configuration::operation_mode::{
         ConfigurationChannel, DeRatingMode, OperationMode,
     };
     pub use crate::commands::configuration::status::Status;
-    pub use crate::commands::configuration::system_control::VoltageOutputControl;
+    pub use crate::commands::configuration::system_control::{
+        CommandVersion, VoltageOutputControl,
+    };
     pub use crate::commands::data_objects::all_source_power_data_object::AllSourceDataPowerDataObject;
     pub use crate::commands::data_objects::extended_power_range_data_object::ExtendedPowerRangeDataObject;
     pub use crate::commands::data_objects::source_power_range_data_object::PeakCurrent;
@@ -29,11 +32,22 @@ pub mod command_structures {
     pub use crate::commands::data_objects::standard_power_range_data_object::StandardPowerRangeDataObject;
     pub use crate::commands::power_delivery::power_delivery_message_result::PowerDeliveryResponse;
     pub use crate::commands::power_delivery::power_delivery_request_message::{
-        OperatingCurrentSelection, PowerDataObject,
+        OperatingCurrentSelection, PowerDataObject, PowerDeliveryRequestMessage,
     };
 }
 pub use crate::errors::*;
 
+use crate::commands::command_map::Command;
+use crate::commands::configuration::status::Status;
+use crate::commands::configuration::system_control::CommandVersion;
+use crate::commands::data_objects::all_source_power_data_object::AllSourceDataPowerDataObject;
+use crate::commands::data_objects::source_power_range_data_object::{
+    PowerType, SourcePowerRangeDataObject,
+};
+use crate::commands::power_delivery::power_delivery_request_message::{
+    OperatingCurrentSelection, PowerDataObject, PowerDeliveryRequestMessage,
+};
+use crate::commands::thermal_resistances::convert_resistance_to_raw_resistance;
 use crate::commands::thermal_resistances::thermal_resistance_25::ThermalResistance25;
 use crate::commands::thermal_resistances::thermal_resistance_50::ThermalResistance50;
 use crate::commands::thermal_resistances::thermal_resistance_75::ThermalResistance75;
@@ -42,7 +56,10 @@ use crate::commands::thresholds::de_rating_threshold::DeRatingThreshold;
 use crate::commands::thresholds::over_current_protection_threshold::OverCurrentProtectionThreshold;
 use crate::commands::thresholds::over_temperature_protection_threshold::OverTemperatureProtectionThreshold;
 use crate::commands::thresholds::over_voltage_protection_threshold::OverVoltageProtectionThreshold;
+use crate::hal::{ErrorKind, NoAcknowledgeSource};
+use crate::thermistor::{Thermistor, ThermistorModel};
 use crate::units::*;
+use core::time::Duration;
 
 /// # Power Delivery Mode Configuration
 ///
@@ -59,7 +76,7 @@ use crate::units::*;
 /// **Get current configuration:**
 /// ```rust
 /// # use ap33772s_rs::{Ap33772s, types::PowerDeliveryMode};
-/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
 /// let pd_mode: PowerDeliveryMode = ap33772s.get_power_delivery_configuration()?;
 /// if pd_mode.programmable_power_supply_adjustable_voltage_supply_enabled {
 ///     println!("PPS with AVS is supported");
@@ -71,7 +88,7 @@ use crate::units::*;
 /// **Set configuration:**
 /// ```rust
 /// # use ap33772s_rs::{Ap33772s, types::PowerDeliveryMode};
-/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
 /// let pd_mode = PowerDeliveryMode {
 ///     programmable_power_supply_adjustable_voltage_supply_enabled: true,
 ///     extended_power_range_mode_enabled: false,
@@ -107,6 +124,400 @@ impl core::fmt::Display for PowerDeliveryMode {
     }
 }
 
+/// # Device Information
+///
+/// Identifies the AP33772S behind the driver, as returned by [`get_device_info`].
+///
+/// The AP33772S has no identification register beyond the command version in the SYSTEM
+/// register, so the board or module it is mounted on cannot be detected.
+///
+/// ## Fields
+///
+/// - `command_version`: The revision of the I2C command set reported by the firmware
+/// - `address`: The 7-bit I2C address the device responded on
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::Ap33772s;
+/// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// let info = device.get_device_info()?;
+/// println!("{info}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`get_device_info`]: crate::Ap33772s::get_device_info
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceInfo {
+    pub command_version: CommandVersion,
+    pub address: u8,
+}
+
+impl core::fmt::Display for DeviceInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "DeviceInfo {{")?;
+        writeln!(f, "  command_version: {:?}", self.command_version)?;
+        writeln!(f, "  address: {:#04x}", self.address)?;
+        write!(f, "}}")
+    }
+}
+
+/// # Device Snapshot
+///
+/// The configuration and contract read back from the device by [`attach`].
+///
+/// ## Fields
+///
+/// - `adopted`: `true` if the device was already running and was left untouched, `false` if it
+///   was fresh from power-on and the default configuration was applied
+/// - `status`: Every STATUS flag seen while checking the boot state. STATUS is cleared on read,
+///   so any UVP, OVP, OCP or OTP event latched before attaching is only reported here
+/// - `power_delivery_mode`: The enabled PPS/AVS and EPR modes
+/// - `thresholds`: The protection thresholds
+/// - `thermal_resistances`: The NTC thermistor resistance table
+/// - `minimum_selection_voltage`: The lowest voltage accepted in autonomous selection
+/// - `requested_voltage` and `requested_current`: The active contract, zero when none has been
+///   negotiated
+///
+/// [`attach`]: crate::Ap33772s::attach
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceSnapshot {
+    pub adopted: bool,
+    pub status: Status,
+    pub power_delivery_mode: PowerDeliveryMode,
+    pub thresholds: Thresholds,
+    pub thermal_resistances: ThermalResistances,
+    pub minimum_selection_voltage: ElectricPotential,
+    pub requested_voltage: ElectricPotential,
+    pub requested_current: ElectricCurrent,
+}
+
+/// # Active Contract
+///
+/// The Power Data Object the device is currently using, as inferred by
+/// [`get_active_contract`] from the requested voltage and current.
+///
+/// The AP33772S does not report the index of the negotiated PDO, so it is found by matching the
+/// requested voltage (VREQ) against the source capabilities. A fixed PDO at exactly the requested
+/// voltage is preferred over a PPS or AVS PDO whose range contains it, so a PPS or AVS request at
+/// exactly the voltage of a fixed PDO is reported as that fixed PDO.
+///
+/// ## Fields
+///
+/// - `power_data_object`: The index of the PDO in use
+/// - `kind`: Whether the contract is fixed, PPS or AVS
+/// - `source_power_range`: The PDO as advertised by the source
+/// - `voltage`: The requested voltage
+/// - `current`: The requested operating current
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::Ap33772s;
+/// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// match device.get_active_contract()? {
+///     // e.g. "PDO4 PPS 9.0 V / 3.00 A"
+///     Some(contract) => println!("{contract}"),
+///     None => println!("No Power Delivery contract"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`get_active_contract`]: crate::Ap33772s::get_active_contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveContract {
+    pub power_data_object: PowerDataObject,
+    pub kind: ContractKind,
+    pub source_power_range: SourcePowerRangeDataObject,
+    pub voltage: ElectricPotential,
+    pub current: ElectricCurrent,
+}
+
+impl ActiveContract {
+    /// Finds the PDO matching the requested voltage, see [`ActiveContract`] for the matching rules
+    pub(crate) fn infer(
+        voltage: ElectricPotential,
+        current: ElectricCurrent,
+        data_objects: &AllSourceDataPowerDataObject,
+    ) -> Option<Self> {
+        let detected = || {
+            data_objects
+                .power_data_objects
+                .iter()
+                .enumerate()
+                .filter(|(_, data_object)| data_object.is_detected())
+        };
+        let fixed = detected().find(|(_, data_object)| {
+            data_object.source_power_type() == PowerType::Fixed
+                && Self::within_resolution(voltage, data_object)
+        });
+        let adjustable = || {
+            detected().find(|(_, data_object)| {
+                data_object.source_power_type() == PowerType::Adjustable
+                    && Self::within_range(voltage, data_object)
+            })
+        };
+        let (index, data_object) = fixed.or_else(adjustable)?;
+
+        let kind = match (data_object, data_object.source_power_type()) {
+            (_, PowerType::Fixed) => ContractKind::Fixed,
+            (SourcePowerRangeDataObject::Standard(_), PowerType::Adjustable) => {
+                ContractKind::ProgrammablePowerSupply
+            }
+            (SourcePowerRangeDataObject::Extended(_), PowerType::Adjustable) => {
+                ContractKind::AdjustableVoltageSupply
+            }
+        };
+        Some(ActiveContract {
+            power_data_object: PowerDataObject::try_from(index).ok()?,
+            kind,
+            source_power_range: data_object.clone(),
+            voltage,
+            current,
+        })
+    }
+
+    fn within_resolution(
+        voltage: ElectricPotential,
+        data_object: &SourcePowerRangeDataObject,
+    ) -> bool {
+        let resolution = f32::from(data_object.voltage_resolution());
+        data_object
+            .get_max_voltage()
+            .is_ok_and(|max_voltage| (max_voltage - voltage).get::<millivolt>().abs() < resolution)
+    }
+
+    fn within_range(voltage: ElectricPotential, data_object: &SourcePowerRangeDataObject) -> bool {
+        let resolution =
+            ElectricPotential::new::<millivolt>(f32::from(data_object.voltage_resolution()));
+        match (data_object.get_min_voltage(), data_object.get_max_voltage()) {
+            (Ok(min_voltage), Ok(max_voltage)) => {
+                voltage > min_voltage - resolution && voltage < max_voltage + resolution
+            }
+            _ => false,
+        }
+    }
+}
+
+impl core::fmt::Display for ActiveContract {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "PDO{} {} {:.1} V / {:.2} A",
+            usize::from(self.power_data_object),
+            self.kind,
+            self.voltage.get::<volt>(),
+            self.current.get::<ampere>()
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ActiveContract {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "PDO{} {} {} V / {} A",
+            usize::from(self.power_data_object),
+            self.kind,
+            self.voltage.get::<volt>(),
+            self.current.get::<ampere>()
+        );
+    }
+}
+
+/// The kind of contract negotiated with a Power Data Object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ContractKind {
+    /// A fixed voltage from a Standard or Extended Power Range PDO
+    Fixed,
+    /// Programmable Power Supply, an adjustable voltage from a Standard Power Range PDO
+    ProgrammablePowerSupply,
+    /// Adjustable Voltage Supply, an adjustable voltage from an Extended Power Range PDO
+    AdjustableVoltageSupply,
+}
+
+impl core::fmt::Display for ContractKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            ContractKind::Fixed => "Fixed",
+            ContractKind::ProgrammablePowerSupply => "PPS",
+            ContractKind::AdjustableVoltageSupply => "AVS",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// # Power Request
+///
+/// A Power Delivery request that can be checked and encoded against the source capabilities
+/// without any communication with the device. [`PowerRequest::validate`] returns the exact
+/// PD_REQMSG that [`crate::Ap33772s::negotiate_power_delivery`] would send, or the reason the
+/// request cannot be made.
+///
+/// The voltage of a PPS or AVS request is rounded to the 100mV or 200mV resolution of the PDO
+/// using the [`Rounding`] policy, [`Rounding::Nearest`] by default. The rounded voltage must lie
+/// within the range of the PDO. The voltage of a Fixed PDO request is ignored.
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::{Ap33772s, types::{PowerRequest, RequestError, Ap33772sError}, types::command_structures::*, units::*};
+/// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// let pdos = device.get_all_source_power_capabilities()?;
+/// let request = PowerRequest::new(
+///     PowerDataObject::StandardPowerRange4,
+///     Some(ElectricPotential::new::<volt>(7.25)),
+///     OperatingCurrentSelection::_2A,
+/// );
+/// match request.validate(&pdos) {
+///     Ok(message) => println!("Voltage code: {}", message.voltage_selection()),
+///     Err(Ap33772sError::InvalidRequest(RequestError::VoltageOutOfRange {
+///         minimum, maximum, ..
+///     })) => println!("Choose a voltage between {minimum} mV and {maximum} mV"),
+///     Err(error) => println!("{error}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerRequest {
+    power_data_object: PowerDataObject,
+    voltage: Option<ElectricPotential>,
+    current: OperatingCurrentSelection,
+    rounding: Rounding,
+}
+
+impl PowerRequest {
+    /// Creates a request for a PDO, the voltage is only needed for a PPS or AVS PDO
+    pub fn new(
+        power_data_object: PowerDataObject,
+        voltage: Option<ElectricPotential>,
+        current: OperatingCurrentSelection,
+    ) -> Self {
+        Self {
+            power_data_object,
+            voltage,
+            current,
+            rounding: Rounding::default(),
+        }
+    }
+
+    /// Sets how the voltage is rounded to the PDO resolution, [`Rounding::Nearest`] by default
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns the requested PDO
+    pub fn power_data_object(&self) -> PowerDataObject {
+        self.power_data_object
+    }
+
+    /// Returns the requested voltage, `None` if none was given
+    pub fn voltage(&self) -> Option<ElectricPotential> {
+        self.voltage
+    }
+
+    /// Returns the requested operating current
+    pub fn current(&self) -> OperatingCurrentSelection {
+        self.current
+    }
+
+    /// Checks the request against the source capabilities and encodes it.
+    ///
+    /// # Errors
+    ///
+    /// - [`Ap33772sError::ExtendedPowerRangeNotEntered`] if a PDO from 8 to 13 is requested before
+    ///   the source sent its EPR capabilities
+    /// - [`Ap33772sError::PowerDataObjectNotDetected`] if the source does not offer the PDO
+    /// - [`RequestError::CurrentOutOfRange`] if the current is above the PDO maximum
+    /// - [`RequestError::MissingArgument`] if a PPS or AVS PDO is requested without a voltage
+    /// - [`RequestError::VoltageOutOfRange`] if the rounded voltage is outside the PDO range
+    /// - [`RequestError::AboveAbsoluteMaximumVoltage`] if the voltage is within the PDO range but
+    ///   above the 21V or 28V the AP33772S supports
+    pub fn validate(
+        &self,
+        data_objects: &AllSourceDataPowerDataObject,
+    ) -> Result<PowerDeliveryRequestMessage, Ap33772sError> {
+        let data_object = data_objects.get_power_data_object(self.power_data_object);
+        if matches!(data_object, SourcePowerRangeDataObject::Extended(_))
+            && !data_objects.extended_power_range_entered()
+        {
+            return Err(Ap33772sError::ExtendedPowerRangeNotEntered);
+        }
+        if !data_object.is_detected() {
+            return Err(Ap33772sError::PowerDataObjectNotDetected(
+                self.power_data_object,
+            ));
+        }
+        let absolute_maximum = data_object.absolute_maximum_voltage().get::<millivolt>();
+
+        let maximum_current = data_object.get_max_current().max_range();
+        if maximum_current < self.current.current() {
+            return Err(Ap33772sError::InvalidRequest(
+                RequestError::CurrentOutOfRange {
+                    requested: self.current.current().get::<milliampere>(),
+                    maximum: maximum_current.get::<milliampere>(),
+                },
+            ));
+        }
+
+        let voltage_selection = if data_object.source_power_type() == PowerType::Fixed {
+            // The voltage of a Fixed PDO is set by the source
+            let voltage = data_object.get_max_voltage()?.get::<millivolt>();
+            if voltage > absolute_maximum {
+                return Err(Ap33772sError::InvalidRequest(
+                    RequestError::AboveAbsoluteMaximumVoltage {
+                        requested: voltage,
+                        maximum: absolute_maximum,
+                    },
+                ));
+            }
+            0
+        } else {
+            let voltage = self
+                .voltage
+                .ok_or(Ap33772sError::InvalidRequest(RequestError::MissingArgument))?;
+            let minimum = data_object.get_min_voltage()?.get::<millivolt>();
+            let maximum = data_object.get_max_voltage()?.get::<millivolt>();
+            let resolution = f32::from(data_object.voltage_resolution());
+            let raw_voltage = self.rounding.round(voltage.get::<millivolt>() / resolution);
+
+            // Also rejects values that do not fit the 8 bit VOLTAGE_SEL
+            let encoded = raw_voltage * resolution;
+            if encoded > absolute_maximum && maximum > absolute_maximum {
+                return Err(Ap33772sError::InvalidRequest(
+                    RequestError::AboveAbsoluteMaximumVoltage {
+                        requested: voltage.get::<millivolt>(),
+                        maximum: absolute_maximum,
+                    },
+                ));
+            }
+            if !(minimum..=maximum).contains(&encoded) {
+                return Err(Ap33772sError::InvalidRequest(
+                    RequestError::VoltageOutOfRange {
+                        requested: voltage.get::<millivolt>(),
+                        minimum,
+                        maximum,
+                    },
+                ));
+            }
+            raw_voltage as u8
+        };
+
+        Ok(PowerDeliveryRequestMessage::builder()
+            .with_voltage_selection(voltage_selection)
+            .with_current_selection(self.current)
+            .with_power_data_object_index(self.power_data_object)
+            .build())
+    }
+}
+
 /// # Device Statistics and Measurements
 ///
 /// Contains real-time operating statistics and measurements from the AP33772S device.
@@ -130,7 +541,7 @@ impl core::fmt::Display for PowerDeliveryMode {
 /// ```rust
 /// # use ap33772s_rs::{Ap33772s, types::Statistics};
 /// # use ap33772s_rs::units::*;
-/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
 /// let stats: Statistics = ap33772s.get_statistics()?;
 ///
 /// println!("Operating: {:.2}V @ {:.2}A = {:.2}W",
@@ -238,7 +649,7 @@ impl defmt::Format for Statistics {
 ///
 /// ```rust
 /// # use ap33772s_rs::{Ap33772s, types::ThermalResistances, units::*};
-/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
 /// // Use default values based on typical NTC characteristics
 /// let thermal_resistances = ThermalResistances::default();
 /// ap33772s.set_thermal_resistances(thermal_resistances)?;
@@ -251,6 +662,10 @@ impl defmt::Format for Statistics {
 ///     _100: ElectricalResistance::new::<ohm>(779.0),
 /// };
 /// ap33772s.set_thermal_resistances(custom_resistances)?;
+///
+/// // Or derive them from the thermistor's datasheet values
+/// let derived_resistances = ThermalResistances::from_beta(ElectricalResistance::new::<ohm>(10000.0), 3950.0)?;
+/// ap33772s.set_thermal_resistances(derived_resistances)?;
 /// # Ok(())
 /// # }
 /// ```
@@ -259,6 +674,8 @@ impl defmt::Format for Statistics {
 ///
 /// - [`crate::Ap33772s::get_thermal_resistances`] - Read current thermal resistance settings
 /// - [`crate::Ap33772s::set_thermal_resistances`] - Update thermal resistance settings
+/// - [`crate::thermistor`] - Thermistor models used by [`ThermalResistances::from_beta`] and
+///   [`ThermalResistances::from_steinhart_hart`]
 ///
 /// [`ElectricalResistance`]: crate::units::ElectricalResistance
 #[derive(Debug, Clone, PartialEq)]
@@ -308,8 +725,123 @@ impl Default for ThermalResistances {
         }
     }
 }
+impl ThermalResistances {
+    /// Derives the thermal resistances from an NTC thermistor's resistance at 25°C and its β value.
+    ///
+    /// # Returns
+    ///
+    /// [`ThermalResistances`] for the thermistor, or [`Ap33772sError::ConversionFailed`] if the
+    /// parameters are invalid or any of the resistances does not fit in the 16-bit TR registers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ap33772s_rs::{types::ThermalResistances, units::*};
+    /// let resistances = ThermalResistances::from_beta(ElectricalResistance::new::<ohm>(10_000.0), 3950.0)?;
+    /// assert!((resistances._50.get::<ohm>() - 3588.0).abs() < 1.0);
+    /// # Ok::<(), ap33772s_rs::types::Ap33772sError>(())
+    /// ```
+    ///
+    /// [`Ap33772sError::ConversionFailed`]: crate::errors::Ap33772sError::ConversionFailed
+    pub fn from_beta(r25: ElectricalResistance, beta: f32) -> Result<Self, Ap33772sError> {
+        Self::from_model(&ThermistorModel::Beta { r25, beta })
+    }
+
+    /// Derives the thermal resistances from the Steinhart–Hart coefficients of an NTC thermistor,
+    /// where `1/T = A + B · ln(R) + C · ln(R)³` with `T` in Kelvin and `R` in ohms.
+    ///
+    /// # Returns
+    ///
+    /// [`ThermalResistances`] for the thermistor, or [`Ap33772sError::ConversionFailed`] if the
+    /// coefficients are invalid or any of the resistances does not fit in the 16-bit TR registers.
+    ///
+    /// [`Ap33772sError::ConversionFailed`]: crate::errors::Ap33772sError::ConversionFailed
+    pub fn from_steinhart_hart(a: f32, b: f32, c: f32) -> Result<Self, Ap33772sError> {
+        Self::from_model(&ThermistorModel::SteinhartHart { a, b, c })
+    }
+
+    /// Derives the thermal resistances for a thermistor from the [`Thermistor`] catalogue.
+    ///
+    /// # Returns
+    ///
+    /// [`ThermalResistances`] for the thermistor, or [`Ap33772sError::ConversionFailed`] if any of the
+    /// resistances does not fit in the 16-bit TR registers.
+    ///
+    /// [`Ap33772sError::ConversionFailed`]: crate::errors::Ap33772sError::ConversionFailed
+    pub fn from_thermistor(thermistor: Thermistor) -> Result<Self, Ap33772sError> {
+        Self::from_model(&thermistor.model())
+    }
+
+    /// Derives the thermal resistances by evaluating a [`ThermistorModel`] at 25°C, 50°C, 75°C and
+    /// 100°C. Every value is checked to fit in the 16-bit TR registers.
+    pub fn from_model(model: &ThermistorModel) -> Result<Self, Ap33772sError> {
+        let resistance_at =
+            |celsius: f32, command: Command| -> Result<ElectricalResistance, Ap33772sError> {
+                let resistance = model
+                    .resistance_at(ThermodynamicTemperature::new::<degree_celsius>(celsius))
+                    .map_err(|error| error.for_register(command))?;
+                convert_resistance_to_raw_resistance(resistance, command)?;
+                Ok(resistance)
+            };
+        Ok(ThermalResistances {
+            _25: resistance_at(25.0, Command::ThermalResistance25)?,
+            _50: resistance_at(50.0, Command::ThermalResistance50)?,
+            _75: resistance_at(75.0, Command::ThermalResistance75)?,
+            _100: resistance_at(100.0, Command::ThermalResistance100)?,
+        })
+    }
+}
 pub use crate::commands::thresholds::under_voltage_protection_threshold::UnderVoltageThreshold;
 
+/// # Rounding Policy
+///
+/// Selects how a physical value is rounded to the resolution of the register it is written to,
+/// for example the 80mV steps of the over voltage threshold or the 50mA steps of the over current
+/// threshold.
+///
+/// Values that are already within floating point error of a register step are always encoded as
+/// that step, so a value read from the device is written back unchanged regardless of the policy.
+///
+/// ## Usage
+///
+/// ```rust
+/// use ap33772s_rs::types::Rounding;
+///
+/// assert_eq!(Rounding::Down.round(12.5), 12.0);
+/// assert_eq!(Rounding::Nearest.round(12.5), 13.0);
+/// assert_eq!(Rounding::Up.round(12.2), 13.0);
+/// assert_eq!(Rounding::Down.round(24.9999), 25.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Rounding {
+    /// Round towards zero, never encoding a value above the one requested
+    Down,
+    /// Round to the nearest register step, halfway values are rounded away from zero
+    #[default]
+    Nearest,
+    /// Round away from zero, never encoding a value below the one requested
+    Up,
+}
+
+impl Rounding {
+    /// The distance in register steps within which a value is treated as exactly on a step
+    const TOLERANCE: f32 = 1e-3;
+
+    /// Rounds a value expressed in register steps to a whole number of steps.
+    pub fn round(self, value: f32) -> f32 {
+        let nearest = libm::roundf(value);
+        if libm::fabsf(value - nearest) <= Self::TOLERANCE {
+            return nearest;
+        }
+        match self {
+            Rounding::Down => libm::floorf(value),
+            Rounding::Nearest => nearest,
+            Rounding::Up => libm::ceilf(value),
+        }
+    }
+}
+
 /// # Protection Thresholds Configuration
 ///
 /// Defines the safety and protection thresholds for the AP33772S device. When any of these
@@ -328,7 +860,7 @@ pub use crate::commands::thresholds::under_voltage_protection_threshold::UnderVo
 ///
 /// ```rust
 /// # use ap33772s_rs::{Ap33772s, types::{Thresholds, UnderVoltageThreshold}, units::*};
-/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// # async fn example(mut ap33772s: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
 /// let thresholds = Thresholds {
 ///     over_voltage: ElectricPotential::new::<volt>(22.0),
 ///     under_voltage: UnderVoltageThreshold::default(),
@@ -346,6 +878,7 @@ pub use crate::commands::thresholds::under_voltage_protection_threshold::UnderVo
 ///
 /// - [`crate::Ap33772s::get_thresholds`] - Read current threshold settings
 /// - [`crate::Ap33772s::set_thresholds`] - Update threshold settings
+/// - [`Thresholds::builder`] - Build thresholds that are validated against the registers
 ///
 /// [`Status`]: crate::types::command_structures::Status
 /// [`ElectricPotential`]: crate::units::ElectricPotential  
@@ -425,3 +958,693 @@ impl Default for Thresholds {
         }
     }
 }
+
+impl Thresholds {
+    /// Creates a [`ThresholdsBuilder`] starting from the register defaults.
+    ///
+    /// The register defaults place both `over_temperature` and `derating` at 120°C, so `derating`
+    /// (or `over_temperature`) has to be set before [`ThresholdsBuilder::build`] accepts them.
+    pub fn builder() -> ThresholdsBuilder {
+        ThresholdsBuilder {
+            thresholds: Thresholds::default(),
+            rounding: Rounding::default(),
+        }
+    }
+}
+
+/// # Validated Thresholds Builder
+///
+/// Builds [`Thresholds`] that are checked against the threshold registers before any
+/// communication with the device. Every value is rounded to the resolution of its register using
+/// the configured [`Rounding`] policy, so the returned [`Thresholds`] are exactly what the device
+/// will store.
+///
+/// ## Validation
+///
+/// - `over_voltage`: offset above the requested voltage, 80mV steps up to 20.4V, must not be zero
+/// - `over_current`: 50mA steps up to 12.75A, zero selects 110% of the negotiated PDO's maximum current
+/// - `over_temperature` and `derating`: whole °C from 0°C to 255°C, `derating` must be below `over_temperature`
+///
+/// Any failure is reported as [`Ap33772sError::InvalidThreshold`] naming the rejected field.
+///
+/// ## Required Fields
+///
+/// Every field starts at its register default, see [`Thresholds::default`]. The OTP and DR
+/// registers both default to 120°C, so `over_temperature` or `derating` must be set before
+/// [`ThresholdsBuilder::build`] succeeds. A builder left at its defaults returns
+/// [`ThresholdError::DeratingNotBelowOverTemperature`].
+///
+/// ## Usage
+///
+/// ```rust
+/// use ap33772s_rs::types::{Rounding, Thresholds};
+/// use ap33772s_rs::units::*;
+///
+/// let thresholds = Thresholds::builder()
+///     .over_voltage(ElectricPotential::new::<millivolt>(1500.0))
+///     .over_current(ElectricCurrent::new::<milliampere>(3210.0))
+///     .over_temperature(ThermodynamicTemperature::new::<degree_celsius>(85.0))
+///     .derating(ThermodynamicTemperature::new::<degree_celsius>(75.0))
+///     .rounding(Rounding::Down)
+///     .build()?;
+/// assert!((thresholds.over_current.get::<milliampere>() - 3200.0).abs() < 0.01);
+/// # Ok::<(), ap33772s_rs::types::Ap33772sError>(())
+/// ```
+///
+/// [`Ap33772sError::InvalidThreshold`]: crate::errors::Ap33772sError::InvalidThreshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdsBuilder {
+    thresholds: Thresholds,
+    rounding: Rounding,
+}
+
+impl ThresholdsBuilder {
+    /// Sets the over voltage threshold, as an offset above the requested voltage
+    pub fn over_voltage(mut self, over_voltage: ElectricPotential) -> Self {
+        self.thresholds.over_voltage = over_voltage;
+        self
+    }
+
+    /// Sets the under voltage threshold, as a percentage of the requested voltage
+    pub fn under_voltage(mut self, under_voltage: UnderVoltageThreshold) -> Self {
+        self.thresholds.under_voltage = under_voltage;
+        self
+    }
+
+    /// Sets the over current threshold
+    pub fn over_current(mut self, over_current: ElectricCurrent) -> Self {
+        self.thresholds.over_current = over_current;
+        self
+    }
+
+    /// Sets the over temperature threshold
+    pub fn over_temperature(mut self, over_temperature: ThermodynamicTemperature) -> Self {
+        self.thresholds.over_temperature = over_temperature;
+        self
+    }
+
+    /// Sets the derating threshold
+    pub fn derating(mut self, derating: ThermodynamicTemperature) -> Self {
+        self.thresholds.derating = derating;
+        self
+    }
+
+    /// Sets how values are rounded to the register resolution, [`Rounding::Nearest`] by default
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Validates the thresholds and rounds them to the register resolution.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Ap33772sError::InvalidThreshold`] naming the rejected field. This includes a builder
+    /// where neither `over_temperature` nor `derating` was set, as their 120°C register defaults are
+    /// equal.
+    pub fn build(self) -> Result<Thresholds, Ap33772sError> {
+        let ThresholdsBuilder {
+            thresholds,
+            rounding,
+        } = self;
+        let out_of_range = |field: ThresholdField| {
+            move |_| Ap33772sError::InvalidThreshold(ThresholdError::OutOfRange(field))
+        };
+
+        let raw_over_voltage =
+            OverVoltageProtectionThreshold::convert_voltage_to_raw_voltage_rounded(
+                thresholds.over_voltage,
+                rounding,
+            )
+            .map_err(out_of_range(ThresholdField::OverVoltage))?;
+        if raw_over_voltage == 0 {
+            return Err(Ap33772sError::InvalidThreshold(
+                ThresholdError::OverVoltageNotAboveRequestedVoltage,
+            ));
+        }
+        let raw_over_current =
+            OverCurrentProtectionThreshold::convert_current_to_raw_current_rounded(
+                thresholds.over_current,
+                rounding,
+            )
+            .map_err(out_of_range(ThresholdField::OverCurrent))?;
+        let raw_over_temperature =
+            OverTemperatureProtectionThreshold::convert_temperature_to_raw_temperature_rounded(
+                thresholds.over_temperature,
+                rounding,
+            )
+            .map_err(out_of_range(ThresholdField::OverTemperature))?;
+        let raw_derating = DeRatingThreshold::convert_temperature_to_raw_temperature_rounded(
+            thresholds.derating,
+            rounding,
+        )
+        .map_err(out_of_range(ThresholdField::Derating))?;
+        if raw_derating >= raw_over_temperature {
+            return Err(Ap33772sError::InvalidThreshold(
+                ThresholdError::DeratingNotBelowOverTemperature,
+            ));
+        }
+
+        Ok(Thresholds {
+            over_voltage: OverVoltageProtectionThreshold::builder()
+                .with_raw_voltage(raw_over_voltage)
+                .build()
+                .voltage()
+                .map_err(out_of_range(ThresholdField::OverVoltage))?,
+            under_voltage: thresholds.under_voltage,
+            over_current: OverCurrentProtectionThreshold::builder()
+                .with_raw_current(raw_over_current)
+                .build()
+                .current()
+                .map_err(out_of_range(ThresholdField::OverCurrent))?,
+            over_temperature:
+                OverTemperatureProtectionThreshold::convert_raw_temperature_to_temperature(
+                    raw_over_temperature,
+                ),
+            derating: DeRatingThreshold::convert_raw_temperature_to_temperature(raw_derating),
+        })
+    }
+}
+
+/// # Threshold Tracking Policy
+///
+/// When enabled with [`crate::Ap33772s::set_threshold_tracking`], the over voltage and over current
+/// thresholds are recalculated for every contract negotiated through
+/// [`crate::Ap33772s::negotiate_power_delivery`] and
+/// [`crate::Ap33772s::negotiate_maximum_power_delivery`].
+///
+/// - `over_voltage`: the OVPTHR offset above the requested voltage is set to
+///   `over_voltage_margin_percentage` of the requested voltage, but never below
+///   `minimum_over_voltage_margin`
+/// - `over_current`: OCPTHR is set to the negotiated operating current, the device trips at 110% of it
+///
+/// Thresholds are only loosened before a transition and only tightened once the new contract has
+/// been accepted, so neither protection trips during the change nor is left loose afterwards. If
+/// the negotiation fails the previous thresholds are restored.
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::{Ap33772s, types::ThresholdTracking};
+/// # async fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) {
+/// device.set_threshold_tracking(Some(ThresholdTracking::default()));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdTracking {
+    /// The over voltage offset as a percentage of the requested voltage
+    pub over_voltage_margin_percentage: f32,
+    /// The smallest over voltage offset that is written, keeping headroom for ripple at low voltages
+    pub minimum_over_voltage_margin: ElectricPotential,
+    /// How the thresholds are rounded to the register resolution
+    pub rounding: Rounding,
+}
+
+impl Default for ThresholdTracking {
+    /// A 10% over voltage margin of at least 500mV, rounded up so the margin is never smaller
+    /// than requested
+    fn default() -> Self {
+        ThresholdTracking {
+            over_voltage_margin_percentage: 10.0,
+            minimum_over_voltage_margin: ElectricPotential::new::<millivolt>(500.0),
+            rounding: Rounding::Up,
+        }
+    }
+}
+
+impl ThresholdTracking {
+    /// Returns the over voltage offset for a contract at `voltage`
+    pub(crate) fn over_voltage(&self, voltage: ElectricPotential) -> ElectricPotential {
+        let margin = voltage * (self.over_voltage_margin_percentage / 100.0);
+        if margin > self.minimum_over_voltage_margin {
+            margin
+        } else {
+            self.minimum_over_voltage_margin
+        }
+    }
+
+    /// Returns the raw OVPTHR and OCPTHR register values for a contract
+    pub(crate) fn raw_thresholds(
+        &self,
+        voltage: ElectricPotential,
+        current: ElectricCurrent,
+    ) -> Result<[u8; 2], Ap33772sError> {
+        Ok([
+            OverVoltageProtectionThreshold::convert_voltage_to_raw_voltage_rounded(
+                self.over_voltage(voltage),
+                self.rounding,
+            )?,
+            OverCurrentProtectionThreshold::convert_current_to_raw_current_rounded(
+                current,
+                self.rounding,
+            )?,
+        ])
+    }
+}
+
+/// # I2C Retry Policy
+///
+/// The AP33772S may NAK while it is busy with PD traffic. A [`RetryPolicy`] set with
+/// [`crate::Ap33772s::set_retry_policy`] repeats a failed I2C transfer when the bus error is one of
+/// the `retryable` kinds, waiting `delay` between attempts. Every transfer of the driver goes through
+/// the policy, and the number of retries is counted by [`crate::Ap33772s::retry_count`].
+///
+/// Retries are disabled by default.
+///
+/// A write that fails after its data has been sent may already have been applied by the device,
+/// retrying it writes the same value again. Writes to the PD_REQMSG and PD_CMDMSG message registers
+/// would send a second request or hard reset instead, so they are never retried, see
+/// [`Command::is_idempotent`](crate::types::command_structures::Command::is_idempotent).
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::{Ap33772s, types::RetryPolicy};
+/// # use core::time::Duration;
+/// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) {
+/// device.set_retry_policy(RetryPolicy {
+///     attempts: 3,
+///     delay: Duration::from_millis(2),
+///     ..RetryPolicy::default()
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// The total number of attempts for each transfer, including the first. `1` disables retries
+    pub attempts: u8,
+    /// The delay between attempts
+    pub delay: Duration,
+    /// The kinds of bus error that are retried
+    pub retryable: &'static [ErrorKind],
+}
+
+impl RetryPolicy {
+    /// The bus errors the AP33772S produces while it is busy: a NAK of the address or data, or a
+    /// lost arbitration on a shared bus
+    pub const TRANSIENT_ERRORS: &'static [ErrorKind] = &[
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+        ErrorKind::ArbitrationLoss,
+    ];
+
+    /// Returns `true` if a bus error of this kind is retried
+    pub fn is_retryable(&self, kind: ErrorKind) -> bool {
+        self.retryable.contains(&kind)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// A single attempt, retrying [`RetryPolicy::TRANSIENT_ERRORS`] once `attempts` is raised
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 1,
+            delay: Duration::from_millis(1),
+            retryable: Self::TRANSIENT_ERRORS,
+        }
+    }
+}
+
+/// # Burst Read
+///
+/// Controls whether [`get_statistics`] reads the statistics registers (0x11 to 0x15) in a single
+/// I2C transfer. The datasheet does not state whether the register address auto-increments across
+/// a read, so by default the driver detects it: a burst read is checked against individual reads of
+/// the requested voltage and current, and the result is remembered. These registers only change when
+/// a new contract is negotiated, unlike the measured values, so a match cannot be missed because a
+/// reading changed between transfers. If a bus error interrupts detection, the statistics are read
+/// individually and detection runs again on the next call.
+///
+/// [`get_statistics`]: crate::Ap33772s::get_statistics
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::{Ap33772s, types::BurstRead};
+/// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c, impl embedded_hal::delay::DelayNs>) {
+/// // Skip detection on a board where burst reads are known to work
+/// device.set_statistics_burst_read(BurstRead::Enabled);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BurstRead {
+    /// Check a burst read against individual reads, then switch to `Enabled` or `Disabled`
+    #[default]
+    Detect,
+    /// Read all statistics in one transfer
+    Enabled,
+    /// Read each statistics register separately
+    Disabled,
+}
+
+/// # Cable Rating
+///
+/// The current the USB-C cable can carry. A source only offers more than 3A when it has detected
+/// a 5A cable, but the AP33772S cannot read the cable's e-marker, so a request for
+/// [`OperatingCurrentSelection::Maximum`] or any current above 3A may be accepted over a passive 3A
+/// cable. A board with a captive cable, or an application that knows the cable in use, sets the
+/// rating with [`crate::Ap33772s::set_cable_rating`] and the driver then caps every current
+/// request and the over current threshold to it.
+///
+/// No rating is set by default, which leaves requests and thresholds unchanged.
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::{Ap33772s, types::CableRating};
+/// # fn example(mut device: Ap33772s<impl embedded_hal::i2c::I2c<Error = embedded_hal::i2c::ErrorKind>, impl embedded_hal::delay::DelayNs>) -> Result<(), Box<dyn std::error::Error>> {
+/// device.set_cable_rating(Some(CableRating::ThreeAmperes));
+///
+/// let pdos = device.get_all_source_power_capabilities()?;
+/// for pdo in CableRating::ThreeAmperes.limited_power_data_objects(&pdos) {
+///     println!("{pdo} is limited to 3A by the cable");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CableRating {
+    /// A passive cable without an e-marker, rated for 3A
+    ThreeAmperes,
+    /// An e-marked cable rated for 5A
+    FiveAmperes,
+}
+
+impl CableRating {
+    /// Returns the current the cable is rated for
+    pub fn maximum_current(&self) -> ElectricCurrent {
+        match self {
+            CableRating::ThreeAmperes => ElectricCurrent::new::<milliampere>(3000.0),
+            CableRating::FiveAmperes => ElectricCurrent::new::<milliampere>(5000.0),
+        }
+    }
+
+    /// Returns the highest current selection the cable can carry that is not above `selection`
+    pub fn cap(&self, selection: OperatingCurrentSelection) -> OperatingCurrentSelection {
+        if selection.current() <= self.maximum_current() {
+            return selection;
+        }
+        OperatingCurrentSelection::from_current(self.maximum_current(), Rounding::Down)
+            .expect("This should not fail, both cable ratings are valid current selections")
+    }
+
+    /// Returns `true` if the PDO offers more current than the cable is rated for, so its full
+    /// current cannot be used
+    pub fn limits(&self, data_object: &SourcePowerRangeDataObject) -> bool {
+        data_object.is_detected()
+            && data_object.get_max_current().min_range() > self.maximum_current()
+    }
+
+    /// Returns the detected PDOs whose full current cannot be used with the cable, see
+    /// [`Self::limits`]
+    pub fn limited_power_data_objects<'a>(
+        &'a self,
+        data_objects: &'a AllSourceDataPowerDataObject,
+    ) -> impl Iterator<Item = PowerDataObject> + 'a {
+        data_objects
+            .power_data_objects
+            .iter()
+            .enumerate()
+            .filter(|(_, data_object)| self.limits(data_object))
+            .filter_map(|(index, _)| PowerDataObject::try_from(index).ok())
+    }
+}
+
+/// The kind of a Power Data Object, see [`SafetyLimits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PowerDataObjectKind {
+    /// A Fixed PDO in the Standard Power Range, up to 20V
+    StandardFixed,
+    /// A Programmable Power Supply APDO in the Standard Power Range
+    ProgrammablePowerSupply,
+    /// A Fixed PDO in the Extended Power Range, 28V and above
+    ExtendedFixed,
+    /// An Adjustable Voltage Supply APDO in the Extended Power Range
+    AdjustableVoltageSupply,
+}
+
+impl From<&SourcePowerRangeDataObject> for PowerDataObjectKind {
+    fn from(data_object: &SourcePowerRangeDataObject) -> Self {
+        match (data_object, data_object.source_power_type()) {
+            (SourcePowerRangeDataObject::Standard(_), PowerType::Fixed) => {
+                PowerDataObjectKind::StandardFixed
+            }
+            (SourcePowerRangeDataObject::Standard(_), PowerType::Adjustable) => {
+                PowerDataObjectKind::ProgrammablePowerSupply
+            }
+            (SourcePowerRangeDataObject::Extended(_), PowerType::Fixed) => {
+                PowerDataObjectKind::ExtendedFixed
+            }
+            (SourcePowerRangeDataObject::Extended(_), PowerType::Adjustable) => {
+                PowerDataObjectKind::AdjustableVoltageSupply
+            }
+        }
+    }
+}
+
+/// The kinds of Power Data Object that may be requested, see [`SafetyLimits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AllowedPowerDataObjects {
+    pub standard_fixed: bool,
+    pub programmable_power_supply: bool,
+    pub extended_fixed: bool,
+    pub adjustable_voltage_supply: bool,
+}
+
+impl Default for AllowedPowerDataObjects {
+    /// Every kind is allowed
+    fn default() -> Self {
+        AllowedPowerDataObjects {
+            standard_fixed: true,
+            programmable_power_supply: true,
+            extended_fixed: true,
+            adjustable_voltage_supply: true,
+        }
+    }
+}
+
+impl AllowedPowerDataObjects {
+    /// Returns `true` if PDOs of this kind may be requested
+    pub fn allows(&self, kind: PowerDataObjectKind) -> bool {
+        match kind {
+            PowerDataObjectKind::StandardFixed => self.standard_fixed,
+            PowerDataObjectKind::ProgrammablePowerSupply => self.programmable_power_supply,
+            PowerDataObjectKind::ExtendedFixed => self.extended_fixed,
+            PowerDataObjectKind::AdjustableVoltageSupply => self.adjustable_voltage_supply,
+        }
+    }
+}
+
+/// # Safety Limits
+///
+/// Hard ceilings for the contracts the driver negotiates and the protection thresholds it writes,
+/// for example the rating of the rail downstream of VOUT. Limits are set when the driver is
+/// created with [`crate::Ap33772s::new_default_with_limits`] or
+/// [`crate::Ap33772s::with_safety_limits`] and cannot be loosened afterwards.
+///
+/// Every request is checked before anything is written. [`crate::Ap33772s::negotiate_power_delivery`]
+/// and [`crate::Ap33772s::negotiate_maximum_power_delivery`] refuse a contract above a limit, and
+/// [`crate::Ap33772s::set_thresholds`] refuses thresholds outside the limits, see
+/// [`SafetyLimits::check_thresholds`]. Thresholds written by [`ThresholdTracking`] are checked
+/// the same way. All return [`Ap33772sError::SafetyLimitExceeded`].
+///
+/// No limits are set by default. Fields left at their default do not limit anything.
+///
+/// ## Usage
+///
+/// ```rust
+/// # use ap33772s_rs::{Ap33772s, types::{AllowedPowerDataObjects, SafetyLimits}, units::*};
+/// # fn example(i2c: impl embedded_hal::i2c::I2c, delay: impl embedded_hal::delay::DelayNs) {
+/// // A 12V rail, EPR is never requested
+/// let device = Ap33772s::new(i2c, delay).with_safety_limits(SafetyLimits {
+///     maximum_voltage: ElectricPotential::new::<volt>(12.0),
+///     maximum_power: Power::new::<watt>(36.0),
+///     allowed_power_data_objects: AllowedPowerDataObjects {
+///         extended_fixed: false,
+///         adjustable_voltage_supply: false,
+///         ..AllowedPowerDataObjects::default()
+///     },
+///     ..SafetyLimits::default()
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafetyLimits {
+    /// The highest voltage that may be requested
+    pub maximum_voltage: ElectricPotential,
+    /// The highest current that may be requested and the highest over current threshold
+    pub maximum_current: ElectricCurrent,
+    /// The highest power, requested voltage times requested current, that may be requested
+    pub maximum_power: Power,
+    /// The kinds of PDO that may be requested
+    pub allowed_power_data_objects: AllowedPowerDataObjects,
+    /// The highest over voltage threshold, as an offset above the requested voltage
+    pub maximum_over_voltage: ElectricPotential,
+    /// The lowest under voltage threshold, as a percentage of the requested voltage
+    pub minimum_under_voltage: UnderVoltageThreshold,
+    /// The highest over temperature and derating threshold
+    pub maximum_temperature: ThermodynamicTemperature,
+}
+
+impl Default for SafetyLimits {
+    /// No limits
+    fn default() -> Self {
+        SafetyLimits {
+            maximum_voltage: ElectricPotential::new::<volt>(f32::INFINITY),
+            maximum_current: ElectricCurrent::new::<ampere>(f32::INFINITY),
+            maximum_power: Power::new::<watt>(f32::INFINITY),
+            allowed_power_data_objects: AllowedPowerDataObjects::default(),
+            maximum_over_voltage: ElectricPotential::new::<volt>(f32::INFINITY),
+            minimum_under_voltage: UnderVoltageThreshold::SeventyPercent,
+            maximum_temperature: ThermodynamicTemperature::new::<degree_celsius>(f32::INFINITY),
+        }
+    }
+}
+
+impl SafetyLimits {
+    /// The amount, in mV, mA or W, by which a value may exceed a limit before it is refused, so
+    /// a value exactly at a limit is not refused because of floating point error
+    const TOLERANCE: f32 = 1e-3;
+
+    /// Returns the stricter of each limit in `self` and `other`
+    pub fn tightened(&self, other: &SafetyLimits) -> SafetyLimits {
+        let allowed = &self.allowed_power_data_objects;
+        let other_allowed = &other.allowed_power_data_objects;
+        SafetyLimits {
+            maximum_voltage: self.maximum_voltage.min(other.maximum_voltage),
+            maximum_current: self.maximum_current.min(other.maximum_current),
+            maximum_power: self.maximum_power.min(other.maximum_power),
+            allowed_power_data_objects: AllowedPowerDataObjects {
+                standard_fixed: allowed.standard_fixed && other_allowed.standard_fixed,
+                programmable_power_supply: allowed.programmable_power_supply
+                    && other_allowed.programmable_power_supply,
+                extended_fixed: allowed.extended_fixed && other_allowed.extended_fixed,
+                adjustable_voltage_supply: allowed.adjustable_voltage_supply
+                    && other_allowed.adjustable_voltage_supply,
+            },
+            maximum_over_voltage: self.maximum_over_voltage.min(other.maximum_over_voltage),
+            minimum_under_voltage: if self.minimum_under_voltage.percentage()
+                >= other.minimum_under_voltage.percentage()
+            {
+                self.minimum_under_voltage
+            } else {
+                other.minimum_under_voltage
+            },
+            maximum_temperature: self.maximum_temperature.min(other.maximum_temperature),
+        }
+    }
+
+    /// Checks a contract of `voltage` and `current` from `data_object` against the limits.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the contract is within every limit, otherwise
+    /// [`Ap33772sError::SafetyLimitExceeded`] with the first limit that is exceeded.
+    pub fn check(
+        &self,
+        data_object: &SourcePowerRangeDataObject,
+        voltage: ElectricPotential,
+        current: ElectricCurrent,
+    ) -> Result<(), Ap33772sError> {
+        let kind = PowerDataObjectKind::from(data_object);
+        if !self.allowed_power_data_objects.allows(kind) {
+            return Err(Ap33772sError::SafetyLimitExceeded(
+                SafetyLimitError::PowerDataObjectKindNotAllowed(kind),
+            ));
+        }
+        if voltage.get::<millivolt>() > self.maximum_voltage.get::<millivolt>() + Self::TOLERANCE {
+            return Err(Ap33772sError::SafetyLimitExceeded(
+                SafetyLimitError::VoltageAboveLimit {
+                    requested: voltage.get::<millivolt>(),
+                    maximum: self.maximum_voltage.get::<millivolt>(),
+                },
+            ));
+        }
+        self.check_current(current)?;
+        let power = voltage * current;
+        if power.get::<watt>() > self.maximum_power.get::<watt>() + Self::TOLERANCE {
+            return Err(Ap33772sError::SafetyLimitExceeded(
+                SafetyLimitError::PowerAboveLimit {
+                    requested: power.get::<watt>(),
+                    maximum: self.maximum_power.get::<watt>(),
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks every field of `thresholds` against the limits. An `over_current` of zero, which
+    /// selects 110% of the negotiated PDO's maximum current, is not checked here as the driver
+    /// replaces it with the current limit before writing it.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the thresholds are within every limit, otherwise
+    /// [`Ap33772sError::SafetyLimitExceeded`] with the first limit that is exceeded.
+    pub fn check_thresholds(&self, thresholds: &Thresholds) -> Result<(), Ap33772sError> {
+        self.check_over_voltage(thresholds.over_voltage)?;
+        if thresholds.under_voltage.percentage() < self.minimum_under_voltage.percentage() {
+            return Err(Ap33772sError::SafetyLimitExceeded(
+                SafetyLimitError::UnderVoltageBelowLimit {
+                    requested: thresholds.under_voltage,
+                    minimum: self.minimum_under_voltage,
+                },
+            ));
+        }
+        self.check_current(thresholds.over_current)?;
+        for (field, temperature) in [
+            (ThresholdField::OverTemperature, thresholds.over_temperature),
+            (ThresholdField::Derating, thresholds.derating),
+        ] {
+            let temperature = temperature.get::<degree_celsius>();
+            let maximum = self.maximum_temperature.get::<degree_celsius>();
+            if temperature > maximum + Self::TOLERANCE {
+                return Err(Ap33772sError::SafetyLimitExceeded(
+                    SafetyLimitError::TemperatureAboveLimit {
+                        field,
+                        requested: temperature,
+                        maximum,
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks an over voltage threshold, as an offset above the requested voltage, against the limit
+    pub(crate) fn check_over_voltage(
+        &self,
+        over_voltage: ElectricPotential,
+    ) -> Result<(), Ap33772sError> {
+        let over_voltage = over_voltage.get::<millivolt>();
+        if over_voltage > self.maximum_over_voltage.get::<millivolt>() + Self::TOLERANCE {
+            return Err(Ap33772sError::SafetyLimitExceeded(
+                SafetyLimitError::OverVoltageAboveLimit {
+                    requested: over_voltage,
+                    maximum: self.maximum_over_voltage.get::<millivolt>(),
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks a current, requested or used as the over current threshold, against the limit
+    pub(crate) fn check_current(&self, current: ElectricCurrent) -> Result<(), Ap33772sError> {
+        if current.get::<milliampere>()
+            > self.maximum_current.get::<milliampere>() + Self::TOLERANCE
+        {
+            return Err(Ap33772sError::SafetyLimitExceeded(
+                SafetyLimitError::CurrentAboveLimit {
+                    requested: current.get::<milliampere>(),
+                    maximum: self.maximum_current.get::<milliampere>(),
+                },
+            ));
+        }
+        Ok(())
+    }
+}
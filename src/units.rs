@@ -12,8 +12,12 @@
 //! - [`ElectricalResistance`] - Resistance measurements (ohms, milliohms)
 //! - [`Power`] - Power measurements (watts)
 //!
+//! ### Ratios
+//! - [`Ratio`] - Dimensionless ratios (percent)
+//!
 //! ### Temperature Measurements  
 //! - [`ThermodynamicTemperature`] - Temperature measurements (celsius)
+//! - [`TemperatureInterval`] - Temperature differences (kelvin)
 //!
 //! ## Unit Constants
 //!
@@ -23,7 +27,9 @@
 //! - **Voltage**: [`volt`], [`millivolt`]
 //! - **Resistance**: [`ohm`], [`milliohm`]
 //! - **Power**: [`watt`]
+//! - **Ratio**: [`percent`]
 //! - **Temperature**: [`degree_celsius`]
+//! - **Temperature difference**: [`kelvin`]
 //!
 //! ## Usage Examples
 //!
@@ -49,6 +55,10 @@ pub use uom::si::f32::ElectricCurrent;
 pub use uom::si::f32::ElectricPotential;
 pub use uom::si::f32::ElectricalResistance;
 pub use uom::si::f32::Power;
+pub use uom::si::f32::Ratio;
+pub use uom::si::f32::TemperatureInterval;
 pub use uom::si::f32::ThermodynamicTemperature;
 pub use uom::si::power::watt;
+pub use uom::si::ratio::percent;
+pub use uom::si::temperature_interval::kelvin;
 pub use uom::si::thermodynamic_temperature::degree_celsius;
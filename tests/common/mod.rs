@@ -0,0 +1,56 @@
+//! Fixtures shared by the integration tests. Each test file only uses some of them.
+#![allow(dead_code)]
+
+use ap33772s_rs::types::command_structures::Command;
+use embedded_hal_mock::eh1::i2c::Transaction;
+
+/// The I2C address of the AP33772S
+pub const ADDRESS: u8 = 0x52;
+
+/// The STATUS flag raised when the source sends new capabilities
+pub const NEW_POWER_DATA_OBJECT: u8 = 0x04;
+
+/// The OPMODE flags for a legacy and a Power Delivery source
+pub const LEGACY: u8 = 0x01;
+pub const POWER_DELIVERY: u8 = 0x02;
+
+/// PDO1 fixed 5V 3A, PDO2 fixed 20V 5A and PDO4 PPS 3.3-21V 5A
+pub const FIXED_5V_3A: [u8; 2] = [0x32, 0xA0];
+pub const FIXED_20V_5A: [u8; 2] = [0xC8, 0xBC];
+pub const PPS_21V_5A: [u8; 2] = [0xD2, 0xFD];
+
+/// Reads the source capabilities with [`FIXED_5V_3A`], [`FIXED_20V_5A`] and [`PPS_21V_5A`]
+pub fn source_capabilities() -> Transaction {
+    let mut data = vec![0x00; 26];
+    data[0..2].copy_from_slice(&FIXED_5V_3A);
+    data[2..4].copy_from_slice(&FIXED_20V_5A);
+    data[6..8].copy_from_slice(&PPS_21V_5A);
+    Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        data,
+    )
+}
+
+pub fn read(command: Command, data: &[u8]) -> Transaction {
+    Transaction::write_read(ADDRESS, vec![command as u8], data.to_vec())
+}
+
+/// Reads OVPTHR and OCPTHR, as snapshotted before the thresholds are changed
+pub fn threshold_snapshot(over_voltage: u8, over_current: u8) -> Vec<Transaction> {
+    vec![
+        read(Command::OverVoltageProtectionThreshold, &[over_voltage]),
+        read(Command::OverCurrentProtectionThreshold, &[over_current]),
+    ]
+}
+
+pub fn read_status(value: u8) -> Transaction {
+    Transaction::write_read(ADDRESS, vec![Command::Status as u8], vec![value])
+}
+
+pub fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "expected {expected}, got {actual}"
+    );
+}
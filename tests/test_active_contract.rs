@@ -0,0 +1,109 @@
+//! Tests for inferring the active contract from the requested voltage and source capabilities.
+//!
+//! ```bash
+//! cargo test --test test_active_contract
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::ContractKind;
+use ap33772s_rs::types::command_structures::{Command, PowerDataObject};
+use common::{ADDRESS, LEGACY, POWER_DELIVERY};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+const THREE_AMPS: [u8; 2] = [0x2C, 0x01];
+
+/// PDO1-3 fixed 5V, 9V and 20V at 3A, PDO4 PPS 3.3-11V at 3A and PDO8 AVS 15-28V
+fn source_capabilities() -> Vec<u8> {
+    let mut data = vec![0x00; 26];
+    data[0..2].copy_from_slice(&[0x32, 0xA0]);
+    data[2..4].copy_from_slice(&[0x5A, 0xA0]);
+    data[4..6].copy_from_slice(&[0xC8, 0xA0]);
+    data[6..8].copy_from_slice(&[0x6E, 0xE1]);
+    data[14..16].copy_from_slice(&[0x8C, 0xFD]);
+    data
+}
+
+fn contract_reads(requested_voltage: [u8; 2]) -> Vec<Transaction> {
+    vec![
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::OperationMode as u8],
+            vec![POWER_DELIVERY],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::VoltageRequested as u8],
+            requested_voltage.to_vec(),
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::CurrentRequested as u8],
+            THREE_AMPS.to_vec(),
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::AllSourcesPowerDataObject as u8],
+            source_capabilities(),
+        ),
+    ]
+}
+
+#[test]
+fn fixed_contract_is_preferred_over_a_containing_pps_range() {
+    // 9V is also inside the PPS range of PDO4
+    let mut i2c = I2cMock::new(&contract_reads([0xB4, 0x00]));
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let contract = device.get_active_contract().unwrap().unwrap();
+    assert_eq!(
+        contract.power_data_object,
+        PowerDataObject::StandardPowerRange2
+    );
+    assert_eq!(contract.kind, ContractKind::Fixed);
+    assert_eq!(contract.to_string(), "PDO2 Fixed 9.0 V / 3.00 A");
+    i2c.done();
+}
+
+#[test]
+fn programmable_power_supply_contract_is_identified() {
+    // 7.2V only matches the PPS range
+    let mut i2c = I2cMock::new(&contract_reads([0x90, 0x00]));
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let contract = device.get_active_contract().unwrap().unwrap();
+    assert_eq!(contract.to_string(), "PDO4 PPS 7.2 V / 3.00 A");
+    i2c.done();
+}
+
+#[test]
+fn adjustable_voltage_supply_contract_is_identified() {
+    // 24V only matches the AVS range of PDO8
+    let mut i2c = I2cMock::new(&contract_reads([0xE0, 0x01]));
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let contract = device.get_active_contract().unwrap().unwrap();
+    assert_eq!(
+        contract.power_data_object,
+        PowerDataObject::ExtendedPowerRange8
+    );
+    assert_eq!(contract.kind, ContractKind::AdjustableVoltageSupply);
+    i2c.done();
+}
+
+#[test]
+fn legacy_source_has_no_contract() {
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::OperationMode as u8],
+        vec![LEGACY],
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_eq!(device.get_active_contract().unwrap(), None);
+    i2c.done();
+}
@@ -0,0 +1,73 @@
+//! Tests for attaching to a device that is already running.
+//!
+//! ```bash
+//! cargo test --test test_attach
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::Ap33772sError;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::units::*;
+use common::{assert_close, read};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+const OVER_VOLTAGE_PROTECTION: u8 = 0x10;
+
+#[test]
+fn running_device_is_adopted_without_writes() {
+    // Only reads are expected, the mock fails on any write or reset
+    let mut expectations = vec![read(Command::SystemControl, &[0x10])];
+    // STATUS was cleared by the reads made before the microcontroller reset, so it is polled
+    // every 5ms until the 100ms boot timeout. An over voltage event latched since then is only
+    // seen on the first read.
+    expectations.push(read(Command::Status, &[OVER_VOLTAGE_PROTECTION]));
+    expectations.extend((0..20).map(|_| read(Command::Status, &[0x00])));
+    expectations.extend([
+        read(Command::PowerDeliveryConfiguration, &[0x03]),
+        read(Command::OverVoltageProtectionThreshold, &[0x19]),
+        read(Command::OverCurrentProtectionThreshold, &[0x00]),
+        read(Command::OverTemperatureProtectionThreshold, &[0x78]),
+        read(Command::UnderVoltageProtectionThreshold, &[0x01]),
+        read(Command::DeRatingThreshold, &[0x78]),
+        read(Command::ThermalResistance25, &[0x10, 0x27]),
+        read(Command::ThermalResistance50, &[0x6A, 0x0E]),
+        read(Command::ThermalResistance75, &[0x43, 0x06]),
+        read(Command::ThermalResistance100, &[0x1C, 0x03]),
+        read(Command::MinimumSelectionVoltage, &[0x19]),
+        // 20V 3A contract
+        read(Command::VoltageRequested, &[0x90, 0x01]),
+        read(Command::CurrentRequested, &[0x2C, 0x01]),
+    ]);
+    let mut i2c = I2cMock::new(&expectations);
+
+    let (_device, snapshot) = Ap33772s::attach(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(snapshot.adopted);
+    assert!(snapshot.status.over_voltage_protection());
+    assert!(!snapshot.status.started());
+    assert!(
+        snapshot
+            .power_delivery_mode
+            .extended_power_range_mode_enabled
+    );
+    assert_close(snapshot.requested_voltage.get::<millivolt>(), 20000.0);
+    assert_close(snapshot.requested_current.get::<milliampere>(), 3000.0);
+    assert_close(snapshot.thermal_resistances._25.get::<ohm>(), 10000.0);
+    i2c.done();
+}
+
+#[test]
+fn missing_device_is_reported() {
+    let expectations = [read(Command::SystemControl, &[0x00])];
+    let mut i2c = I2cMock::new(&expectations);
+
+    let result = Ap33772s::attach(i2c.clone(), NoopDelay::new());
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::WrongCommandVersion(0x00))
+    ));
+    i2c.done();
+}
@@ -0,0 +1,128 @@
+//! Tests for predicting and configuring the PDO selected by the AP33772S without a host request.
+//!
+//! ```bash
+//! cargo test --test test_autonomous_selection
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::selection::predict_autonomous_selection;
+use ap33772s_rs::types::PowerDeliveryMode;
+use ap33772s_rs::types::command_structures::{
+    AllSourceDataPowerDataObject, Command, PowerDataObject,
+};
+use ap33772s_rs::units::*;
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+const EXTENDED_POWER_RANGE_ENABLED: PowerDeliveryMode = PowerDeliveryMode {
+    programmable_power_supply_adjustable_voltage_supply_enabled: true,
+    extended_power_range_mode_enabled: true,
+};
+
+/// PDO1-3 fixed 5V, 9V and 20V at 3A
+fn source_capabilities() -> Vec<u8> {
+    let mut data = vec![0x00; 26];
+    data[0..2].copy_from_slice(&[0x32, 0xA0]);
+    data[2..4].copy_from_slice(&[0x5A, 0xA0]);
+    data[4..6].copy_from_slice(&[0xC8, 0xA0]);
+    data
+}
+
+fn read_capabilities(data: Vec<u8>) -> AllSourceDataPowerDataObject {
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        data,
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let capabilities = device.get_all_source_power_capabilities().unwrap();
+    i2c.done();
+    capabilities
+}
+
+#[test]
+fn default_minimum_selection_voltage_enables_the_5v_output() {
+    let pdos = read_capabilities(source_capabilities());
+
+    let selection = predict_autonomous_selection(
+        &pdos,
+        ElectricPotential::new::<volt>(5.0),
+        &EXTENDED_POWER_RANGE_ENABLED,
+    )
+    .unwrap();
+    assert_eq!(
+        selection.power_data_object,
+        Some(PowerDataObject::StandardPowerRange1)
+    );
+    assert!(selection.output_enabled);
+    assert!(selection.extended_power_range_entry);
+    assert_eq!(selection.to_string(), "PDO1 5.0 V, output on");
+}
+
+#[test]
+fn higher_minimum_selection_voltage_keeps_the_output_off() {
+    // A 20V PDO is available, but the AP33772S still requests PDO1 on its own
+    let pdos = read_capabilities(source_capabilities());
+
+    let selection = predict_autonomous_selection(
+        &pdos,
+        ElectricPotential::new::<volt>(9.0),
+        &EXTENDED_POWER_RANGE_ENABLED,
+    )
+    .unwrap();
+    assert_eq!(selection.to_string(), "PDO1 5.0 V, output off");
+}
+
+#[test]
+fn source_without_power_delivery_is_used_at_5v() {
+    let pdos = read_capabilities(vec![0x00; 26]);
+
+    let selection = predict_autonomous_selection(
+        &pdos,
+        ElectricPotential::new::<volt>(5.0),
+        &EXTENDED_POWER_RANGE_ENABLED,
+    )
+    .unwrap();
+    assert_eq!(selection.power_data_object, None);
+    assert!(selection.output_enabled);
+    assert!(!selection.extended_power_range_entry);
+}
+
+#[test]
+fn configuration_rounds_the_minimum_voltage_up() {
+    let expectations = [
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryConfiguration as u8, 0x02],
+        ),
+        // 9.1V is rounded up to 9.2V
+        Transaction::write(ADDRESS, vec![Command::MinimumSelectionVoltage as u8, 0x2E]),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    device
+        .configure_autonomous_selection(ElectricPotential::new::<volt>(9.1), true, false)
+        .unwrap();
+    i2c.done();
+}
+
+#[test]
+fn minimum_selection_voltage_is_not_truncated() {
+    let expectations = [Transaction::write(
+        ADDRESS,
+        vec![Command::MinimumSelectionVoltage as u8, 0x19],
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    device
+        .set_minimum_selection_voltage(ElectricPotential::new::<volt>(5.0))
+        .unwrap();
+    i2c.done();
+}
@@ -0,0 +1,58 @@
+//! Tests for the context recorded with I2C bus errors.
+//!
+//! ```bash
+//! cargo test --test test_bus_errors
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::types::{Ap33772sError, Operation, Thresholds};
+use common::ADDRESS;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+#[test]
+fn failed_read_records_register_and_bus_error() {
+    let bus_error = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let expectations = [
+        Transaction::write_read(ADDRESS, vec![Command::Status as u8], vec![0x00])
+            .with_error(bus_error),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_eq!(
+        device.get_status(),
+        Err(Ap33772sError::I2c {
+            command: Command::Status,
+            operation: Operation::Read,
+            source: bus_error,
+        })
+    );
+    i2c.done();
+}
+
+#[test]
+fn failed_write_records_register_and_bus_error() {
+    let expectations = [Transaction::write(
+        ADDRESS,
+        vec![Command::OverVoltageProtectionThreshold as u8, 0x19],
+    )
+    .with_error(ErrorKind::Bus)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_eq!(
+        device.set_thresholds(Thresholds::default()),
+        Err(Ap33772sError::I2c {
+            command: Command::OverVoltageProtectionThreshold,
+            operation: Operation::Write,
+            source: ErrorKind::Bus,
+        })
+    );
+    i2c.done();
+}
@@ -0,0 +1,174 @@
+//! Tests for capping current requests and the over current threshold to the cable rating.
+//!
+//! ```bash
+//! cargo test --test test_cable_rating
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::{
+    AllSourceDataPowerDataObject, Command, OperatingCurrentSelection, PowerDataObject,
+    PowerDeliveryResponse,
+};
+use ap33772s_rs::types::{Ap33772sError, CableRating, RequestError, Thresholds};
+use common::{ADDRESS, source_capabilities};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+fn request(message: [u8; 2]) -> Vec<Transaction> {
+    vec![
+        Transaction::write(
+            ADDRESS,
+            vec![
+                Command::PowerDeliveryRequestMessage as u8,
+                message[0],
+                message[1],
+            ],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryMessageResult as u8],
+            vec![0x01],
+        ),
+    ]
+}
+
+fn new_device(expectations: &[Transaction]) -> (I2cMock, Ap33772s<I2cMock, NoopDelay>) {
+    let i2c = I2cMock::new(expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_cable_rating(Some(CableRating::ThreeAmperes));
+    (i2c, device)
+}
+
+#[test]
+fn current_request_is_capped_to_cable_rating() {
+    let mut expectations = vec![source_capabilities()];
+    // PDO2 at 3A instead of 5A
+    expectations.extend(request([0x00, 0x28]));
+    let (mut i2c, mut device) = new_device(&expectations);
+
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let response = device
+        .negotiate_power_delivery(
+            PowerDataObject::StandardPowerRange2,
+            None,
+            OperatingCurrentSelection::Maximum,
+            &data_objects,
+        )
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
+
+#[test]
+fn maximum_request_keeps_maximum_voltage_at_cable_rating() {
+    let mut expectations = vec![source_capabilities()];
+    // PDO4 at 21V and 3A, the special 0x4FFF message would request 5A
+    expectations.extend(request([0xD2, 0x48]));
+    let (mut i2c, mut device) = new_device(&expectations);
+
+    let response = device
+        .negotiate_maximum_power_delivery(PowerDataObject::StandardPowerRange4)
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
+
+#[test]
+fn maximum_request_within_cable_rating_is_unchanged() {
+    let mut expectations = vec![source_capabilities()];
+    expectations.extend(request([0xFF, 0x1F]));
+    let (mut i2c, mut device) = new_device(&expectations);
+
+    let response = device
+        .negotiate_maximum_power_delivery(PowerDataObject::StandardPowerRange1)
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
+
+#[test]
+fn maximum_request_at_cable_rating_is_validated() {
+    // PDO13 fixed 48V 5A, above the 28V the AP33772S supports
+    let mut data = vec![0x00; 26];
+    data[24..26].copy_from_slice(&[0xF0, 0xBC]);
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        data,
+    )];
+    let (mut i2c, mut device) = new_device(&expectations);
+
+    let result = device.negotiate_maximum_power_delivery(PowerDataObject::ExtendedPowerRange13);
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::InvalidRequest(
+            RequestError::AboveAbsoluteMaximumVoltage { .. }
+        ))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn over_current_threshold_is_capped_to_cable_rating() {
+    let expectations = [
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, 0x19],
+        ),
+        // Zero would select 110% of a 5A PDO, 3A is written instead
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverCurrentProtectionThreshold as u8, 0x3C],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::UnderVoltageProtectionThreshold as u8, 0x00],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverTemperatureProtectionThreshold as u8, 0x78],
+        ),
+        Transaction::write(ADDRESS, vec![Command::DeRatingThreshold as u8, 0x78]),
+    ];
+    let (mut i2c, mut device) = new_device(&expectations);
+
+    device.set_thresholds(Thresholds::default()).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn power_data_objects_above_cable_rating_are_flagged() {
+    let expectations = [source_capabilities()];
+    let (mut i2c, mut device) = new_device(&expectations);
+    let data_objects: AllSourceDataPowerDataObject =
+        device.get_all_source_power_capabilities().unwrap();
+    i2c.done();
+
+    let limited: Vec<_> = CableRating::ThreeAmperes
+        .limited_power_data_objects(&data_objects)
+        .collect();
+    assert_eq!(
+        limited,
+        [
+            PowerDataObject::StandardPowerRange2,
+            PowerDataObject::StandardPowerRange4
+        ]
+    );
+    assert_eq!(
+        CableRating::FiveAmperes
+            .limited_power_data_objects(&data_objects)
+            .count(),
+        0
+    );
+    assert_eq!(
+        CableRating::ThreeAmperes.cap(OperatingCurrentSelection::_4_5A),
+        OperatingCurrentSelection::_3A
+    );
+    assert_eq!(
+        CableRating::ThreeAmperes.cap(OperatingCurrentSelection::_2A),
+        OperatingCurrentSelection::_2A
+    );
+}
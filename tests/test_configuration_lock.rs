@@ -0,0 +1,155 @@
+//! Tests for locking the safety relevant registers against later writes.
+//!
+//! ```bash
+//! cargo test --test test_configuration_lock
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::{Command, OperatingCurrentSelection, PowerDataObject};
+use ap33772s_rs::types::{
+    Ap33772sError, PowerDeliveryMode, ThermalResistances, ThresholdTracking, Thresholds,
+};
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// The locked registers holding their power-on defaults, with a 3A OCP threshold
+fn locked_registers(over_current: u8) -> Vec<Transaction> {
+    let one_byte = [
+        (Command::SystemConfiguration, 0xF8),
+        (Command::PowerDeliveryConfiguration, 0x03),
+        (Command::OverVoltageProtectionThreshold, 0x19),
+        (Command::OverCurrentProtectionThreshold, over_current),
+        (Command::UnderVoltageProtectionThreshold, 0x01),
+        (Command::OverTemperatureProtectionThreshold, 0x78),
+        (Command::DeRatingThreshold, 0x78),
+    ];
+    let thermal_resistances = [
+        (Command::ThermalResistance25, [0x28, 0x00]),
+        (Command::ThermalResistance50, [0x9A, 0x10]),
+        (Command::ThermalResistance75, [0x8C, 0x06]),
+        (Command::ThermalResistance100, [0xDB, 0x02]),
+    ];
+    one_byte
+        .into_iter()
+        .map(|(command, value)| Transaction::write_read(ADDRESS, vec![command as u8], vec![value]))
+        .chain(thermal_resistances.into_iter().map(|(command, value)| {
+            Transaction::write_read(ADDRESS, vec![command as u8], value.to_vec())
+        }))
+        .collect()
+}
+
+fn locked_device(expectations: &[Transaction]) -> (I2cMock, Ap33772s<I2cMock, NoopDelay>) {
+    let i2c = I2cMock::new(expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.lock_configuration().unwrap();
+    assert!(device.is_configuration_locked());
+    (i2c, device)
+}
+
+#[test]
+fn locked_registers_are_refused_without_bus_traffic() {
+    let expectations = locked_registers(0x3C);
+    let (mut i2c, mut device) = locked_device(&expectations);
+
+    assert_eq!(
+        device.set_thresholds(Thresholds::default()),
+        Err(Ap33772sError::ConfigurationLocked(
+            Command::OverVoltageProtectionThreshold
+        ))
+    );
+    assert_eq!(
+        device.set_thresholds_with_rollback(Thresholds::default()),
+        Err(Ap33772sError::ConfigurationLocked(
+            Command::OverVoltageProtectionThreshold
+        ))
+    );
+    assert_eq!(
+        device.set_thermal_resistances(ThermalResistances::default()),
+        Err(Ap33772sError::ConfigurationLocked(
+            Command::ThermalResistance25
+        ))
+    );
+    assert_eq!(
+        device.set_power_delivery_mode(PowerDeliveryMode {
+            programmable_power_supply_adjustable_voltage_supply_enabled: true,
+            extended_power_range_mode_enabled: false,
+        }),
+        Err(Ap33772sError::ConfigurationLocked(
+            Command::PowerDeliveryConfiguration
+        ))
+    );
+    i2c.done();
+}
+
+#[test]
+fn unlocked_configuration_can_be_written() {
+    let mut expectations = locked_registers(0x3C);
+    expectations.push(Transaction::write(
+        ADDRESS,
+        vec![Command::PowerDeliveryConfiguration as u8, 0x02],
+    ));
+    let (mut i2c, mut device) = locked_device(&expectations);
+
+    device.unlock_configuration_allowing_protection_changes();
+    assert!(!device.is_configuration_locked());
+    device
+        .set_power_delivery_mode(PowerDeliveryMode {
+            programmable_power_supply_adjustable_voltage_supply_enabled: true,
+            extended_power_range_mode_enabled: false,
+        })
+        .unwrap();
+    i2c.done();
+}
+
+#[test]
+fn verification_reports_changed_register() {
+    let mut expectations = locked_registers(0x3C);
+    expectations.extend(locked_registers(0x3C));
+    // The device was reset and OCPTHR is back at its default
+    expectations.extend(locked_registers(0x00));
+    let (mut i2c, mut device) = locked_device(&expectations);
+
+    device.verify_locked_configuration().unwrap();
+    assert_eq!(
+        device.verify_locked_configuration(),
+        Err(Ap33772sError::LockedConfigurationChanged {
+            command: Command::OverCurrentProtectionThreshold,
+            expected: 0x3C,
+            actual: 0x00,
+        })
+    );
+    i2c.done();
+}
+
+#[test]
+fn threshold_tracking_is_refused_before_request() {
+    let mut capabilities = vec![0x00; 26];
+    capabilities[0..2].copy_from_slice(&[0x32, 0xA0]);
+    let mut expectations = locked_registers(0x3C);
+    expectations.push(Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        capabilities,
+    ));
+    let (mut i2c, mut device) = locked_device(&expectations);
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let result = device.negotiate_power_delivery(
+        PowerDataObject::StandardPowerRange1,
+        None,
+        OperatingCurrentSelection::_3A,
+        &data_objects,
+    );
+    assert_eq!(
+        result,
+        Err(Ap33772sError::ConfigurationLocked(
+            Command::OverVoltageProtectionThreshold
+        ))
+    );
+    i2c.done();
+}
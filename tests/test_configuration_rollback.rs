@@ -0,0 +1,159 @@
+//! Tests for the transactional configuration setters which restore the previous register
+//! values when a write fails. These tests use a mocked I2C bus so no hardware is required.
+//!
+//! ```bash
+//! cargo test --test test_configuration_rollback
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::types::{
+    Ap33772sError, Operation, SafetyLimitError, SafetyLimits, ThermalResistances, Thresholds,
+};
+use ap33772s_rs::units::*;
+use common::{ADDRESS, read, threshold_snapshot};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// Thresholds chosen so that every value lands in the middle of a register step
+fn new_thresholds() -> Thresholds {
+    Thresholds {
+        over_voltage: ElectricPotential::new::<millivolt>(2040.0),
+        over_current: ElectricCurrent::new::<milliampere>(3025.0),
+        ..Thresholds::default()
+    }
+}
+
+/// Reads every threshold register, as snapshotted by `set_thresholds_with_rollback`
+fn all_thresholds_snapshot() -> Vec<Transaction> {
+    let mut snapshot = threshold_snapshot(0x20, 0x28);
+    snapshot.extend([
+        read(Command::UnderVoltageProtectionThreshold, &[0x02]),
+        read(Command::OverTemperatureProtectionThreshold, &[0x50]),
+        read(Command::DeRatingThreshold, &[0x46]),
+    ]);
+    snapshot
+}
+
+#[test]
+fn failed_threshold_write_restores_previous_values() {
+    let mut expectations = all_thresholds_snapshot();
+    expectations.extend([
+        // New thresholds, the over current write fails
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, 0x19],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverCurrentProtectionThreshold as u8, 0x3C],
+        )
+        .with_error(ErrorKind::Other),
+        // Rollback of every register in the snapshot
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, 0x20],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverCurrentProtectionThreshold as u8, 0x28],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::UnderVoltageProtectionThreshold as u8, 0x02],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverTemperatureProtectionThreshold as u8, 0x50],
+        ),
+        Transaction::write(ADDRESS, vec![Command::DeRatingThreshold as u8, 0x46]),
+    ]);
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let result = device.set_thresholds_with_rollback(new_thresholds());
+    assert_eq!(
+        result,
+        Err(Ap33772sError::I2c {
+            command: Command::OverCurrentProtectionThreshold,
+            operation: Operation::Write,
+            source: ErrorKind::Other,
+        })
+    );
+    i2c.done();
+}
+
+#[test]
+fn failed_rollback_reports_both_errors() {
+    let mut expectations = all_thresholds_snapshot();
+    expectations.extend([
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, 0x19],
+        )
+        .with_error(ErrorKind::Other),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, 0x20],
+        )
+        .with_error(ErrorKind::Bus),
+    ]);
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let result = device.set_thresholds_with_rollback(new_thresholds());
+    assert_eq!(
+        result,
+        Err(Ap33772sError::RollbackFailed {
+            command: Some(Command::OverVoltageProtectionThreshold),
+            source: Some(ErrorKind::Other),
+            rollback_command: Command::OverVoltageProtectionThreshold,
+            rollback_source: Some(ErrorKind::Bus),
+        })
+    );
+    i2c.done();
+}
+
+#[test]
+fn refused_thresholds_are_returned_without_a_rollback() {
+    let expectations = [];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device =
+        Ap33772s::new(i2c.clone(), NoopDelay::new()).with_safety_limits(SafetyLimits {
+            maximum_over_voltage: ElectricPotential::new::<millivolt>(800.0),
+            ..SafetyLimits::default()
+        });
+
+    let result = device.set_thresholds_with_rollback(new_thresholds());
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::OverVoltageAboveLimit { .. }
+        ))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn unconvertible_resistance_is_returned_without_a_rollback() {
+    let expectations = [];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let result = device.set_thermal_resistances_with_rollback(ThermalResistances {
+        _100: ElectricalResistance::new::<ohm>(-1.0),
+        ..ThermalResistances::default()
+    });
+    assert_eq!(
+        result,
+        Err(Ap33772sError::ConversionFailed {
+            command: Some(Command::ThermalResistance100),
+            value: -1.0,
+        })
+    );
+    i2c.done();
+}
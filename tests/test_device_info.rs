@@ -0,0 +1,50 @@
+//! Tests for identifying the device through its command version.
+//!
+//! ```bash
+//! cargo test --test test_device_info
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::{Command, CommandVersion};
+use ap33772s_rs::types::{Ap33772sError, DeviceInfo};
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+fn read_system_control(value: u8) -> Transaction {
+    Transaction::write_read(ADDRESS, vec![Command::SystemControl as u8], vec![value])
+}
+
+#[test]
+fn device_info_reports_command_version_and_address() {
+    let expectations = [read_system_control(0x10)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let info = device.get_device_info().unwrap();
+    assert_eq!(
+        info,
+        DeviceInfo {
+            command_version: CommandVersion::V1_0,
+            address: 0x52,
+        }
+    );
+    i2c.done();
+}
+
+#[test]
+fn unknown_command_version_is_refused() {
+    // CMDVER = 2 is reserved for a future revision of the command set
+    let expectations = [read_system_control(0x20)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert!(matches!(
+        device.get_device_info(),
+        Err(Ap33772sError::WrongCommandVersion(0x20))
+    ));
+    i2c.done();
+}
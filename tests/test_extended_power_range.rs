@@ -0,0 +1,191 @@
+//! Tests for entering EPR Mode and requesting Extended Power Range PDOs.
+//!
+//! ```bash
+//! cargo test --test test_extended_power_range
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::{
+    AllSourceDataPowerDataObject, Command, OperatingCurrentSelection, PowerDataObject,
+};
+use ap33772s_rs::types::{Ap33772sError, PowerRequest, RequestError};
+use common::{ADDRESS, NEW_POWER_DATA_OBJECT, read_status};
+use core::time::Duration;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// PDO1 fixed 5V 3A only
+fn standard_capabilities() -> Vec<u8> {
+    let mut data = vec![0x00; 26];
+    data[0..2].copy_from_slice(&[0x32, 0xA0]);
+    data
+}
+
+/// PDO1 fixed 5V 3A, PDO8 fixed 28V 3A and PDO9 fixed 36V 3A
+fn extended_capabilities() -> Vec<u8> {
+    let mut data = standard_capabilities();
+    data[14..16].copy_from_slice(&[0x8C, 0xA0]);
+    data[16..18].copy_from_slice(&[0xB4, 0xA0]);
+    data
+}
+
+fn read_capabilities(data: Vec<u8>) -> Transaction {
+    Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        data,
+    )
+}
+
+fn capabilities(data: Vec<u8>) -> AllSourceDataPowerDataObject {
+    let mut i2c = I2cMock::new(&[read_capabilities(data)]);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let capabilities = device.get_all_source_power_capabilities().unwrap();
+    i2c.done();
+    capabilities
+}
+
+fn fixed_request(power_data_object: PowerDataObject) -> PowerRequest {
+    PowerRequest::new(power_data_object, None, OperatingCurrentSelection::_3A)
+}
+
+#[test]
+fn extended_pdo_is_rejected_before_epr_mode_is_entered() {
+    let pdos = capabilities(standard_capabilities());
+    assert!(!pdos.extended_power_range_entered());
+
+    assert_eq!(
+        fixed_request(PowerDataObject::ExtendedPowerRange8).validate(&pdos),
+        Err(Ap33772sError::ExtendedPowerRangeNotEntered)
+    );
+}
+
+#[test]
+fn extended_pdo_above_28v_is_rejected() {
+    let pdos = capabilities(extended_capabilities());
+    assert!(pdos.extended_power_range_entered());
+
+    assert!(
+        fixed_request(PowerDataObject::ExtendedPowerRange8)
+            .validate(&pdos)
+            .is_ok()
+    );
+    let error = fixed_request(PowerDataObject::ExtendedPowerRange9)
+        .validate(&pdos)
+        .unwrap_err();
+    let Ap33772sError::InvalidRequest(RequestError::AboveAbsoluteMaximumVoltage {
+        requested,
+        maximum,
+    }) = error
+    else {
+        panic!("expected AboveAbsoluteMaximumVoltage, got {error:?}");
+    };
+    assert!((requested - 36000.0).abs() < 0.01);
+    assert!((maximum - 28000.0).abs() < 0.01);
+}
+
+#[test]
+fn entered_epr_mode_is_returned_immediately() {
+    let mut i2c = I2cMock::new(&[read_capabilities(extended_capabilities())]);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdos = device
+        .enter_extended_power_range(Duration::from_millis(100))
+        .unwrap();
+    assert!(pdos.extended_power_range_entered());
+    i2c.done();
+}
+
+#[test]
+fn epr_mode_is_enabled_and_new_capabilities_are_awaited() {
+    let expectations = [
+        read_capabilities(standard_capabilities()),
+        // PPS enabled, EPR disabled
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryConfiguration as u8],
+            vec![0x02],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryConfiguration as u8, 0x03],
+        ),
+        read_status(0x00),
+        read_status(NEW_POWER_DATA_OBJECT),
+        read_capabilities(extended_capabilities()),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdos = device
+        .enter_extended_power_range(Duration::from_millis(100))
+        .unwrap();
+    assert!(
+        pdos.get_power_data_object(PowerDataObject::ExtendedPowerRange8)
+            .is_detected()
+    );
+    i2c.done();
+}
+
+#[test]
+fn source_without_epr_times_out() {
+    let expectations = [
+        read_capabilities(standard_capabilities()),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryConfiguration as u8],
+            vec![0x03],
+        ),
+        read_status(0x00),
+        read_status(0x00),
+        read_status(0x00),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_eq!(
+        device.enter_extended_power_range(Duration::from_millis(10)),
+        Err(Ap33772sError::ExtendedPowerRangeNotEntered)
+    );
+    i2c.done();
+}
+
+#[test]
+fn last_extended_power_data_object_can_be_indexed() {
+    // PDO13 fixed 48V 3A, the last of the 13 PDO registers
+    let mut data = standard_capabilities();
+    data[24..26].copy_from_slice(&[0xF0, 0xA0]);
+    let pdos = capabilities(data);
+
+    assert!(
+        pdos.get_power_data_object(PowerDataObject::ExtendedPowerRange13)
+            .is_detected()
+    );
+}
+
+#[test]
+fn leaving_epr_mode_after_a_hard_reset_is_detected() {
+    let expectations = [
+        read_capabilities(extended_capabilities()),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryCommandMessage as u8, 0x01],
+        ),
+        // The source is back in SPR and only offers PDO1
+        read_capabilities(standard_capabilities()),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdos = device.check_extended_power_range().unwrap();
+    assert!(pdos.extended_power_range_entered());
+    device.hard_reset().unwrap();
+    assert_eq!(
+        device.check_extended_power_range(),
+        Err(Ap33772sError::ExtendedPowerRangeNotEntered)
+    );
+    i2c.done();
+}
@@ -0,0 +1,103 @@
+//! Tests for converting currents to the operating current selection of a request.
+//!
+//! ```bash
+//! cargo test --test test_operating_current_selection
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::Rounding;
+use ap33772s_rs::types::command_structures::{
+    Command, OperatingCurrentSelection, PowerDataObject, SourcePowerRangeDataObject,
+};
+use ap33772s_rs::units::*;
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+fn selection(milliamperes: f32, rounding: Rounding) -> Option<OperatingCurrentSelection> {
+    OperatingCurrentSelection::from_current(
+        ElectricCurrent::new::<milliampere>(milliamperes),
+        rounding,
+    )
+    .ok()
+}
+
+fn read_power_data_object(data: [u8; 2]) -> SourcePowerRangeDataObject {
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceStandardPowerRange1 as u8],
+        data.to_vec(),
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let data_object = device
+        .get_source_power_capability(PowerDataObject::StandardPowerRange1)
+        .unwrap();
+    i2c.done();
+    data_object
+}
+
+#[test]
+fn currents_are_rounded_to_250ma_steps() {
+    use OperatingCurrentSelection::*;
+    assert_eq!(selection(3000.0, Rounding::Down), Some(_3A));
+    assert_eq!(selection(3100.0, Rounding::Down), Some(_3A));
+    assert_eq!(selection(3100.0, Rounding::Nearest), Some(_3A));
+    assert_eq!(selection(3125.0, Rounding::Nearest), Some(_3_25A));
+    assert_eq!(selection(3100.0, Rounding::Up), Some(_3_25A));
+}
+
+#[test]
+fn maximum_is_500ma_above_the_last_step() {
+    use OperatingCurrentSelection::*;
+    assert_eq!(selection(4700.0, Rounding::Nearest), Some(_4_5A));
+    assert_eq!(selection(4800.0, Rounding::Nearest), Some(Maximum));
+    assert_eq!(selection(4600.0, Rounding::Up), Some(Maximum));
+    assert_eq!(selection(4900.0, Rounding::Down), Some(_4_5A));
+}
+
+#[test]
+fn currents_outside_the_selections_are_rejected_unless_rounded_in() {
+    use OperatingCurrentSelection::*;
+    assert_eq!(selection(500.0, Rounding::Up), Some(_1A));
+    assert_eq!(selection(900.0, Rounding::Nearest), Some(_1A));
+    assert_eq!(selection(900.0, Rounding::Down), None);
+    assert_eq!(selection(6000.0, Rounding::Down), Some(Maximum));
+    assert_eq!(selection(5100.0, Rounding::Up), None);
+    assert_eq!(selection(f32::NAN, Rounding::Nearest), None);
+}
+
+#[test]
+fn highest_allowed_selection_stays_within_the_advertised_current() {
+    // 5V fixed PDOs advertising 3.00-3.24A, 1.50-1.74A and 5A or more
+    let three_amps = read_power_data_object([0x32, 0xA0]);
+    let one_and_a_half_amps = read_power_data_object([0x32, 0x88]);
+    let five_amps = read_power_data_object([0x32, 0xBC]);
+
+    assert_eq!(
+        OperatingCurrentSelection::highest_allowed_for(&three_amps),
+        Some(OperatingCurrentSelection::_3A)
+    );
+    assert_eq!(
+        OperatingCurrentSelection::highest_allowed_for(&one_and_a_half_amps),
+        Some(OperatingCurrentSelection::_1_5A)
+    );
+    assert_eq!(
+        OperatingCurrentSelection::highest_allowed_for(&five_amps),
+        Some(OperatingCurrentSelection::Maximum)
+    );
+}
+
+#[test]
+fn no_selection_is_allowed_below_one_ampere() {
+    // A 5V fixed PDO advertising less than 1.24A, which may be below the lowest 1A selection
+    let below_one_and_a_quarter_amps = read_power_data_object([0x32, 0x80]);
+
+    assert_eq!(
+        OperatingCurrentSelection::highest_allowed_for(&below_one_and_a_quarter_amps),
+        None
+    );
+}
@@ -0,0 +1,158 @@
+//! Tests for validating and encoding Power Delivery requests without touching the bus.
+//!
+//! ```bash
+//! cargo test --test test_power_request
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::{
+    AllSourceDataPowerDataObject, Command, OperatingCurrentSelection, PowerDataObject,
+};
+use ap33772s_rs::types::{Ap33772sError, PowerRequest, RequestError, Rounding};
+use ap33772s_rs::units::*;
+use common::{ADDRESS, assert_close};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// PDO1 fixed 5V 3A, PDO4 PPS 3.3-11V 3A and PDO8 AVS 15-28V 5A
+fn source_capabilities() -> AllSourceDataPowerDataObject {
+    let mut data = vec![0x00; 26];
+    data[0..2].copy_from_slice(&[0x32, 0xA0]);
+    data[6..8].copy_from_slice(&[0x6E, 0xE1]);
+    data[14..16].copy_from_slice(&[0x8C, 0xFD]);
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        data,
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let capabilities = device.get_all_source_power_capabilities().unwrap();
+    i2c.done();
+    capabilities
+}
+
+#[test]
+fn programmable_power_supply_voltage_is_rounded_to_100mv() {
+    let pdos = source_capabilities();
+
+    let nearest = PowerRequest::new(
+        PowerDataObject::StandardPowerRange4,
+        Some(ElectricPotential::new::<millivolt>(7260.0)),
+        OperatingCurrentSelection::_2A,
+    );
+    let message = nearest.validate(&pdos).unwrap();
+    assert_eq!(message.voltage_selection(), 73);
+    assert_eq!(message.current_selection(), OperatingCurrentSelection::_2A);
+    assert_eq!(
+        message.power_data_object_index(),
+        Ok(PowerDataObject::StandardPowerRange4)
+    );
+    assert_eq!(message.raw_value(), 0x4449);
+
+    let down = nearest.rounding(Rounding::Down).validate(&pdos).unwrap();
+    assert_eq!(down.voltage_selection(), 72);
+}
+
+#[test]
+fn adjustable_voltage_supply_voltage_uses_200mv_steps() {
+    let pdos = source_capabilities();
+
+    let message = PowerRequest::new(
+        PowerDataObject::ExtendedPowerRange8,
+        Some(ElectricPotential::new::<volt>(24.0)),
+        OperatingCurrentSelection::_4A,
+    )
+    .validate(&pdos)
+    .unwrap();
+    assert_eq!(message.voltage_selection(), 120);
+}
+
+#[test]
+fn fixed_request_ignores_the_voltage() {
+    let pdos = source_capabilities();
+
+    let message = PowerRequest::new(
+        PowerDataObject::StandardPowerRange1,
+        Some(ElectricPotential::new::<volt>(12.0)),
+        OperatingCurrentSelection::_3A,
+    )
+    .validate(&pdos)
+    .unwrap();
+    assert_eq!(message.voltage_selection(), 0);
+}
+
+#[test]
+fn voltage_outside_the_range_reports_the_limits() {
+    let pdos = source_capabilities();
+
+    let error = PowerRequest::new(
+        PowerDataObject::StandardPowerRange4,
+        Some(ElectricPotential::new::<volt>(12.0)),
+        OperatingCurrentSelection::_2A,
+    )
+    .validate(&pdos)
+    .unwrap_err();
+    let Ap33772sError::InvalidRequest(RequestError::VoltageOutOfRange {
+        requested,
+        minimum,
+        maximum,
+    }) = error
+    else {
+        panic!("expected VoltageOutOfRange, got {error:?}");
+    };
+    assert_close(requested, 12000.0);
+    assert_close(minimum, 3300.0);
+    assert_close(maximum, 11000.0);
+}
+
+#[test]
+fn current_above_the_maximum_reports_the_limit() {
+    let pdos = source_capabilities();
+
+    // The 3A code of PDO1 covers currents up to 3.24A
+    let error = PowerRequest::new(
+        PowerDataObject::StandardPowerRange1,
+        None,
+        OperatingCurrentSelection::_4A,
+    )
+    .validate(&pdos)
+    .unwrap_err();
+    let Ap33772sError::InvalidRequest(RequestError::CurrentOutOfRange { requested, maximum }) =
+        error
+    else {
+        panic!("expected CurrentOutOfRange, got {error:?}");
+    };
+    assert_close(requested, 4000.0);
+    assert_close(maximum, 3240.0);
+}
+
+#[test]
+fn missing_pdo_and_voltage_are_rejected() {
+    let pdos = source_capabilities();
+
+    let missing_pdo = PowerRequest::new(
+        PowerDataObject::StandardPowerRange2,
+        None,
+        OperatingCurrentSelection::_1A,
+    );
+    assert_eq!(
+        missing_pdo.validate(&pdos),
+        Err(Ap33772sError::PowerDataObjectNotDetected(
+            PowerDataObject::StandardPowerRange2
+        ))
+    );
+
+    let missing_voltage = PowerRequest::new(
+        PowerDataObject::StandardPowerRange4,
+        None,
+        OperatingCurrentSelection::_1A,
+    );
+    assert_eq!(
+        missing_voltage.validate(&pdos),
+        Err(Ap33772sError::InvalidRequest(RequestError::MissingArgument))
+    );
+}
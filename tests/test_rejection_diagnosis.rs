@@ -0,0 +1,158 @@
+//! Tests for listing the likely causes of a rejected Power Delivery request.
+//!
+//! ```bash
+//! cargo test --test test_rejection_diagnosis
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::diagnosis::{RejectionCause, RejectionDiagnosis};
+use ap33772s_rs::types::PowerRequest;
+use ap33772s_rs::types::command_structures::{
+    Command, OperatingCurrentSelection, PowerDataObject, PowerDeliveryResponse,
+};
+use ap33772s_rs::units::*;
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+const EXTENDED_POWER_RANGE_ENABLED: u8 = 0x01;
+const ADJUSTABLE_SUPPLY_ENABLED: u8 = 0x02;
+/// 5V in 200mV steps
+const DEFAULT_MINIMUM_SELECTION_VOLTAGE: u8 = 0x19;
+
+/// PDO1 fixed 5V 3A and PDO4 PPS 3.3-11V 3A, no EPR PDOs
+fn source_capabilities() -> Vec<u8> {
+    let mut data = vec![0x00; 26];
+    data[0..2].copy_from_slice(&[0x32, 0xA0]);
+    data[6..8].copy_from_slice(&[0x6E, 0xE1]);
+    data
+}
+
+fn diagnose(
+    request: PowerRequest,
+    response: PowerDeliveryResponse,
+    power_delivery_configuration: u8,
+    minimum_selection_voltage: u8,
+) -> RejectionDiagnosis {
+    let expectations = [
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::AllSourcesPowerDataObject as u8],
+            source_capabilities(),
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryConfiguration as u8],
+            vec![power_delivery_configuration],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::MinimumSelectionVoltage as u8],
+            vec![minimum_selection_voltage],
+        ),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let diagnosis = device.diagnose_rejection(&request, response).unwrap();
+    i2c.done();
+    diagnosis
+}
+
+fn extended_power_range_request() -> PowerRequest {
+    PowerRequest::new(
+        PowerDataObject::ExtendedPowerRange8,
+        None,
+        OperatingCurrentSelection::_3A,
+    )
+}
+
+#[test]
+fn extended_power_range_pdo_with_epr_disabled() {
+    let diagnosis = diagnose(
+        extended_power_range_request(),
+        PowerDeliveryResponse::Invalid,
+        ADJUSTABLE_SUPPLY_ENABLED,
+        DEFAULT_MINIMUM_SELECTION_VOLTAGE,
+    );
+    let causes: Vec<_> = diagnosis.iter().copied().collect();
+    assert_eq!(
+        causes,
+        [
+            RejectionCause::ExtendedPowerRangeDisabled,
+            RejectionCause::PowerDataObjectNotDetected
+        ]
+    );
+    assert_eq!(
+        diagnosis.to_string(),
+        "- EPR PDO requested but EPR mode disabled in PDCONFIG\n\
+         - The requested PDO is not offered by the source"
+    );
+}
+
+#[test]
+fn extended_power_range_pdo_without_epr_source() {
+    let diagnosis = diagnose(
+        extended_power_range_request(),
+        PowerDeliveryResponse::Invalid,
+        EXTENDED_POWER_RANGE_ENABLED | ADJUSTABLE_SUPPLY_ENABLED,
+        DEFAULT_MINIMUM_SELECTION_VOLTAGE,
+    );
+    assert!(diagnosis.contains(&RejectionCause::ExtendedPowerRangeNotEntered));
+    assert!(!diagnosis.contains(&RejectionCause::ExtendedPowerRangeDisabled));
+}
+
+#[test]
+fn programmable_power_supply_request_lists_every_cause() {
+    let request = PowerRequest::new(
+        PowerDataObject::StandardPowerRange4,
+        Some(ElectricPotential::new::<volt>(12.0)),
+        OperatingCurrentSelection::_2A,
+    );
+    // PPS disabled and VSELMIN at 15V
+    let diagnosis = diagnose(
+        request,
+        PowerDeliveryResponse::NotSupported,
+        EXTENDED_POWER_RANGE_ENABLED,
+        0x4B,
+    );
+    let causes: Vec<_> = diagnosis.iter().collect();
+    assert_eq!(causes.len(), 3);
+    assert_eq!(causes[0], &RejectionCause::AdjustableSupplyDisabled);
+    assert!(matches!(
+        causes[1],
+        RejectionCause::VoltageOutOfRange { .. }
+    ));
+    assert!(matches!(
+        causes[2],
+        RejectionCause::BelowMinimumSelectionVoltage { .. }
+    ));
+}
+
+#[test]
+fn failed_transaction_of_a_valid_request() {
+    let request = PowerRequest::new(
+        PowerDataObject::StandardPowerRange1,
+        None,
+        OperatingCurrentSelection::_3A,
+    );
+    let diagnosis = diagnose(
+        request.clone(),
+        PowerDeliveryResponse::TransactionFailed,
+        EXTENDED_POWER_RANGE_ENABLED | ADJUSTABLE_SUPPLY_ENABLED,
+        DEFAULT_MINIMUM_SELECTION_VOLTAGE,
+    );
+    let causes: Vec<_> = diagnosis.iter().copied().collect();
+    assert_eq!(causes, [RejectionCause::NoGoodCrc]);
+
+    let diagnosis = diagnose(
+        request,
+        PowerDeliveryResponse::NotSupported,
+        EXTENDED_POWER_RANGE_ENABLED | ADJUSTABLE_SUPPLY_ENABLED,
+        DEFAULT_MINIMUM_SELECTION_VOLTAGE,
+    );
+    assert!(diagnosis.is_empty());
+    assert_eq!(diagnosis.to_string(), "No likely cause found");
+}
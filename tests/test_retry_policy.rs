@@ -0,0 +1,126 @@
+//! Tests for retrying I2C transfers that fail with transient bus errors.
+//!
+//! ```bash
+//! cargo test --test test_retry_policy
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::types::{Ap33772sError, Operation, RetryPolicy};
+use common::ADDRESS;
+use core::time::Duration;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+const BUSY: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+
+fn read_status() -> Transaction {
+    Transaction::write_read(ADDRESS, vec![Command::Status as u8], vec![0x00])
+}
+
+fn three_attempts() -> RetryPolicy {
+    RetryPolicy {
+        attempts: 3,
+        delay: Duration::from_micros(500),
+        ..RetryPolicy::default()
+    }
+}
+
+#[test]
+fn transient_errors_are_retried_and_counted() {
+    let expectations = [
+        read_status().with_error(BUSY),
+        read_status().with_error(BUSY),
+        read_status(),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_retry_policy(three_attempts());
+
+    device.get_status().unwrap();
+    assert_eq!(device.retry_count(), 2);
+    device.reset_retry_count();
+    assert_eq!(device.retry_count(), 0);
+    i2c.done();
+}
+
+#[test]
+fn retries_stop_after_the_last_attempt() {
+    let expectations = [
+        read_status().with_error(BUSY),
+        read_status().with_error(BUSY),
+        read_status().with_error(BUSY),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_retry_policy(three_attempts());
+
+    assert_eq!(
+        device.get_status(),
+        Err(Ap33772sError::I2c {
+            command: Command::Status,
+            operation: Operation::Read,
+            source: BUSY,
+        })
+    );
+    assert_eq!(device.retry_count(), 2);
+    i2c.done();
+}
+
+#[test]
+fn other_errors_are_not_retried() {
+    let expectations = [read_status().with_error(ErrorKind::Bus)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_retry_policy(three_attempts());
+
+    assert!(matches!(
+        device.get_status(),
+        Err(Ap33772sError::I2c {
+            source: ErrorKind::Bus,
+            ..
+        })
+    ));
+    assert_eq!(device.retry_count(), 0);
+    i2c.done();
+}
+
+#[test]
+fn retries_are_disabled_by_default() {
+    let expectations = [read_status().with_error(BUSY)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert!(device.get_status().is_err());
+    assert_eq!(device.retry_count(), 0);
+    i2c.done();
+}
+
+#[test]
+fn power_delivery_messages_are_not_retried() {
+    let expectations = [Transaction::write(
+        ADDRESS,
+        vec![Command::PowerDeliveryCommandMessage as u8, 0x01],
+    )
+    .with_error(BUSY)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_retry_policy(three_attempts());
+
+    assert_eq!(
+        device.hard_reset(),
+        Err(Ap33772sError::I2c {
+            command: Command::PowerDeliveryCommandMessage,
+            operation: Operation::Write,
+            source: BUSY,
+        })
+    );
+    assert_eq!(device.retry_count(), 0);
+    assert!(!Command::PowerDeliveryRequestMessage.is_idempotent());
+    assert!(Command::OverCurrentProtectionThreshold.is_idempotent());
+    i2c.done();
+}
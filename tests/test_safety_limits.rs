@@ -0,0 +1,365 @@
+//! Tests for refusing requests and thresholds outside the application's safety limits.
+//!
+//! ```bash
+//! cargo test --test test_safety_limits
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::{
+    Command, OperatingCurrentSelection, PowerDataObject, PowerDeliveryResponse,
+};
+use ap33772s_rs::types::{
+    AllowedPowerDataObjects, Ap33772sError, PowerDataObjectKind, SafetyLimitError, SafetyLimits,
+    ThresholdField, ThresholdTracking, Thresholds, UnderVoltageThreshold,
+};
+use ap33772s_rs::units::*;
+use common::{ADDRESS, assert_close, source_capabilities};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// A 12V 36W rail
+fn twelve_volt_rail() -> SafetyLimits {
+    SafetyLimits {
+        maximum_voltage: ElectricPotential::new::<volt>(12.0),
+        maximum_current: ElectricCurrent::new::<ampere>(3.0),
+        maximum_power: Power::new::<watt>(36.0),
+        ..SafetyLimits::default()
+    }
+}
+
+fn new_device(
+    expectations: &[Transaction],
+    limits: SafetyLimits,
+) -> (I2cMock, Ap33772s<I2cMock, NoopDelay>) {
+    let i2c = I2cMock::new(expectations);
+    let device = Ap33772s::new(i2c.clone(), NoopDelay::new()).with_safety_limits(limits);
+    (i2c, device)
+}
+
+#[test]
+fn fixed_voltage_above_limit_is_refused_before_writing() {
+    let expectations = [source_capabilities()];
+    let (mut i2c, mut device) = new_device(&expectations, twelve_volt_rail());
+
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let Err(Ap33772sError::SafetyLimitExceeded(SafetyLimitError::VoltageAboveLimit {
+        requested,
+        maximum,
+    })) = device.negotiate_power_delivery(
+        PowerDataObject::StandardPowerRange2,
+        None,
+        OperatingCurrentSelection::_3A,
+        &data_objects,
+    )
+    else {
+        panic!("expected the 20V PDO to be refused");
+    };
+    assert_close(requested, 20000.0);
+    assert_close(maximum, 12000.0);
+    i2c.done();
+}
+
+#[test]
+fn maximum_power_delivery_is_checked_against_limits() {
+    let expectations = [source_capabilities()];
+    let (mut i2c, mut device) = new_device(&expectations, twelve_volt_rail());
+
+    let result = device.negotiate_maximum_power_delivery(PowerDataObject::StandardPowerRange2);
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::VoltageAboveLimit { .. }
+        ))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn maximum_power_delivery_is_refused_before_thresholds_are_tracked() {
+    // Only the capabilities are read, OVPTHR and OCPTHR are never loosened for the 20V PDO
+    let expectations = [source_capabilities()];
+    let (mut i2c, mut device) = new_device(&expectations, twelve_volt_rail());
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+
+    let result = device.negotiate_maximum_power_delivery(PowerDataObject::StandardPowerRange2);
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::VoltageAboveLimit { .. }
+        ))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn tracked_thresholds_are_checked_against_limits() {
+    let expectations = [source_capabilities()];
+    let limits = SafetyLimits {
+        maximum_over_voltage: ElectricPotential::new::<millivolt>(800.0),
+        ..SafetyLimits::default()
+    };
+    let (mut i2c, mut device) = new_device(&expectations, limits);
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+
+    // 10% of 20V is a 2000mV offset, refused before the thresholds are read or written
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let Err(Ap33772sError::SafetyLimitExceeded(SafetyLimitError::OverVoltageAboveLimit {
+        requested,
+        maximum,
+    })) = device.negotiate_power_delivery(
+        PowerDataObject::StandardPowerRange2,
+        None,
+        OperatingCurrentSelection::_3A,
+        &data_objects,
+    )
+    else {
+        panic!("expected the tracked over voltage threshold to be refused");
+    };
+    assert_close(requested, 2000.0);
+    assert_close(maximum, 800.0);
+    i2c.done();
+}
+
+#[test]
+fn programmable_power_supply_is_limited_by_power() {
+    let expectations = [
+        source_capabilities(),
+        // PDO4 at 12V and 3A
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryRequestMessage as u8, 0x78, 0x48],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryMessageResult as u8],
+            vec![0x01],
+        ),
+    ];
+    let limits = SafetyLimits {
+        maximum_current: ElectricCurrent::new::<ampere>(5.0),
+        ..twelve_volt_rail()
+    };
+    let (mut i2c, mut device) = new_device(&expectations, limits);
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let voltage = Some(ElectricPotential::new::<volt>(12.0));
+
+    let Err(Ap33772sError::SafetyLimitExceeded(SafetyLimitError::PowerAboveLimit {
+        requested,
+        maximum,
+    })) = device.negotiate_power_delivery(
+        PowerDataObject::StandardPowerRange4,
+        voltage,
+        OperatingCurrentSelection::_4A,
+        &data_objects,
+    )
+    else {
+        panic!("expected 48W to be refused");
+    };
+    assert_close(requested, 48.0);
+    assert_close(maximum, 36.0);
+
+    let response = device
+        .negotiate_power_delivery(
+            PowerDataObject::StandardPowerRange4,
+            voltage,
+            OperatingCurrentSelection::_3A,
+            &data_objects,
+        )
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
+
+#[test]
+fn disallowed_power_data_object_kind_is_refused() {
+    let expectations = [source_capabilities()];
+    let limits = SafetyLimits {
+        allowed_power_data_objects: AllowedPowerDataObjects {
+            programmable_power_supply: false,
+            ..AllowedPowerDataObjects::default()
+        },
+        ..SafetyLimits::default()
+    };
+    let (mut i2c, mut device) = new_device(&expectations, limits);
+
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let result = device.negotiate_power_delivery(
+        PowerDataObject::StandardPowerRange4,
+        Some(ElectricPotential::new::<volt>(9.0)),
+        OperatingCurrentSelection::_2A,
+        &data_objects,
+    );
+    assert_eq!(
+        result,
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::PowerDataObjectKindNotAllowed(
+                PowerDataObjectKind::ProgrammablePowerSupply
+            )
+        ))
+    );
+    i2c.done();
+}
+
+#[test]
+fn over_current_threshold_above_limit_is_refused() {
+    let expectations = [
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, 0x19],
+        ),
+        // Zero would select 110% of the PDO maximum current, the 3A limit is written instead
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverCurrentProtectionThreshold as u8, 0x3C],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::UnderVoltageProtectionThreshold as u8, 0x00],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverTemperatureProtectionThreshold as u8, 0x78],
+        ),
+        Transaction::write(ADDRESS, vec![Command::DeRatingThreshold as u8, 0x78]),
+    ];
+    let (mut i2c, mut device) = new_device(&expectations, twelve_volt_rail());
+
+    let result = device.set_thresholds(Thresholds {
+        over_current: ElectricCurrent::new::<ampere>(4.0),
+        ..Thresholds::default()
+    });
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::CurrentAboveLimit { .. }
+        ))
+    ));
+    device.set_thresholds(Thresholds::default()).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn limits_can_only_be_tightened() {
+    let (mut i2c, device) = new_device(&[], twelve_volt_rail());
+    let device = device.with_safety_limits(SafetyLimits {
+        maximum_voltage: ElectricPotential::new::<volt>(20.0),
+        maximum_current: ElectricCurrent::new::<ampere>(2.0),
+        ..SafetyLimits::default()
+    });
+
+    let limits = device.safety_limits().unwrap();
+    assert_close(limits.maximum_voltage.get::<volt>(), 12.0);
+    assert_close(limits.maximum_current.get::<ampere>(), 2.0);
+    assert_close(limits.maximum_power.get::<watt>(), 36.0);
+    i2c.done();
+}
+
+#[test]
+fn every_threshold_field_is_checked() {
+    let limits = SafetyLimits {
+        maximum_over_voltage: ElectricPotential::new::<volt>(1.0),
+        minimum_under_voltage: UnderVoltageThreshold::SeventyFivePercent,
+        maximum_temperature: ThermodynamicTemperature::new::<degree_celsius>(100.0),
+        ..SafetyLimits::default()
+    };
+    let (mut i2c, mut device) = new_device(&[], limits);
+    let within_limits = Thresholds {
+        over_voltage: ElectricPotential::new::<volt>(1.0),
+        over_temperature: ThermodynamicTemperature::new::<degree_celsius>(100.0),
+        derating: ThermodynamicTemperature::new::<degree_celsius>(90.0),
+        ..Thresholds::default()
+    };
+
+    assert!(matches!(
+        device.set_thresholds(Thresholds {
+            over_voltage: ElectricPotential::new::<volt>(2.0),
+            ..within_limits.clone()
+        }),
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::OverVoltageAboveLimit { .. }
+        ))
+    ));
+    assert_eq!(
+        device.set_thresholds(Thresholds {
+            under_voltage: UnderVoltageThreshold::SeventyPercent,
+            ..within_limits.clone()
+        }),
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::UnderVoltageBelowLimit {
+                requested: UnderVoltageThreshold::SeventyPercent,
+                minimum: UnderVoltageThreshold::SeventyFivePercent,
+            }
+        ))
+    );
+    assert!(matches!(
+        device.set_thresholds(Thresholds {
+            over_temperature: ThermodynamicTemperature::new::<degree_celsius>(120.0),
+            ..within_limits.clone()
+        }),
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::TemperatureAboveLimit {
+                field: ThresholdField::OverTemperature,
+                ..
+            }
+        ))
+    ));
+    assert!(matches!(
+        device.set_thresholds(Thresholds {
+            derating: ThermodynamicTemperature::new::<degree_celsius>(110.0),
+            ..within_limits
+        }),
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::TemperatureAboveLimit {
+                field: ThresholdField::Derating,
+                ..
+            }
+        ))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn limits_apply_to_the_default_thresholds() {
+    let expectations = [
+        Transaction::write_read(ADDRESS, vec![Command::SystemControl as u8], vec![0x10]),
+        // STARTED, I2C_READY and NEWPDO, the device is fresh from power-on
+        Transaction::write_read(ADDRESS, vec![Command::Status as u8], vec![0x07]),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance25 as u8, 0x10, 0x27],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance50 as u8, 0x41, 0x10],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance75 as u8, 0x88, 0x07],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance100 as u8, 0xCE, 0x03],
+        ),
+        // The 120°C default thresholds are refused before they are written
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+
+    let result = Ap33772s::new_default_with_limits(
+        i2c.clone(),
+        NoopDelay::new(),
+        SafetyLimits {
+            maximum_temperature: ThermodynamicTemperature::new::<degree_celsius>(100.0),
+            ..SafetyLimits::default()
+        },
+    );
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::SafetyLimitExceeded(
+            SafetyLimitError::TemperatureAboveLimit { .. }
+        ))
+    ));
+    i2c.done();
+}
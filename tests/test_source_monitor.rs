@@ -0,0 +1,106 @@
+//! Tests for tracking source attachment by polling OPMODE and STATUS.
+//!
+//! ```bash
+//! cargo test --test test_source_monitor
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::monitor::{Orientation, SourceEvent, SourceKind, SourceMonitor};
+use ap33772s_rs::types::command_structures::Command;
+use common::{ADDRESS, LEGACY, NEW_POWER_DATA_OBJECT, POWER_DELIVERY};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+const CC2: u8 = 0x80;
+
+fn poll(operation_mode: u8, status: u8) -> [Transaction; 2] {
+    [
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::OperationMode as u8],
+            vec![operation_mode],
+        ),
+        Transaction::write_read(ADDRESS, vec![Command::Status as u8], vec![status]),
+    ]
+}
+
+#[test]
+fn power_delivery_attach_reports_capabilities_on_the_next_call() {
+    let expectations = [
+        poll(0x00, 0x00),
+        poll(POWER_DELIVERY | CC2, NEW_POWER_DATA_OBJECT),
+        poll(POWER_DELIVERY | CC2, 0x00),
+    ]
+    .concat();
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let mut monitor = SourceMonitor::new();
+
+    assert_eq!(monitor.poll(&mut device).unwrap(), None);
+    assert_eq!(
+        monitor.poll(&mut device).unwrap(),
+        Some(SourceEvent::Attached {
+            kind: SourceKind::PowerDelivery,
+            orientation: Orientation::Cc2,
+        })
+    );
+    // Queued from the previous read, no I2C traffic
+    assert_eq!(
+        monitor.poll(&mut device).unwrap(),
+        Some(SourceEvent::CapabilitiesChanged)
+    );
+    assert_eq!(monitor.poll(&mut device).unwrap(), None);
+    assert_eq!(
+        monitor.attached(),
+        Some((SourceKind::PowerDelivery, Orientation::Cc2))
+    );
+    i2c.done();
+}
+
+#[test]
+fn legacy_source_attach_and_detach() {
+    let expectations = [poll(LEGACY, 0x00), poll(0x00, 0x00)].concat();
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let mut monitor = SourceMonitor::new();
+
+    assert_eq!(
+        monitor.poll(&mut device).unwrap(),
+        Some(SourceEvent::Attached {
+            kind: SourceKind::Legacy,
+            orientation: Orientation::Cc1,
+        })
+    );
+    assert_eq!(
+        monitor.poll(&mut device).unwrap(),
+        Some(SourceEvent::Detached)
+    );
+    assert_eq!(monitor.attached(), None);
+    i2c.done();
+}
+
+#[test]
+fn new_capabilities_from_an_attached_source_are_reported() {
+    let expectations = [
+        poll(POWER_DELIVERY, 0x00),
+        poll(POWER_DELIVERY, NEW_POWER_DATA_OBJECT),
+    ]
+    .concat();
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    let mut monitor = SourceMonitor::new();
+
+    assert!(matches!(
+        monitor.poll(&mut device).unwrap(),
+        Some(SourceEvent::Attached { .. })
+    ));
+    assert_eq!(
+        monitor.poll(&mut device).unwrap(),
+        Some(SourceEvent::CapabilitiesChanged)
+    );
+    assert!(monitor.last_status().new_power_data_object());
+    i2c.done();
+}
@@ -0,0 +1,107 @@
+//! Tests for reading a single Power Data Object from its own register.
+//!
+//! ```bash
+//! cargo test --test test_source_power_capability
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::Ap33772sError;
+use ap33772s_rs::types::command_structures::{
+    Command, PowerDataObject, SourcePowerRangeDataObject,
+};
+use ap33772s_rs::units::*;
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+fn assert_max_voltage(pdo: &SourcePowerRangeDataObject, expected_millivolts: f32) {
+    let actual = pdo.get_max_voltage().unwrap().get::<millivolt>();
+    assert!(
+        (actual - expected_millivolts).abs() < 0.01,
+        "expected {expected_millivolts}mV, got {actual}mV"
+    );
+}
+
+#[test]
+fn standard_pdo_is_read_from_its_own_register() {
+    // 20V 3A fixed PDO
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceStandardPowerRange4 as u8],
+        vec![0xC8, 0xA0],
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdo = device
+        .get_source_power_capability(PowerDataObject::StandardPowerRange4)
+        .unwrap();
+    assert!(matches!(pdo, SourcePowerRangeDataObject::Standard(_)));
+    assert!(pdo.is_detected());
+    assert_max_voltage(&pdo, 20000.0);
+    i2c.done();
+}
+
+#[test]
+fn extended_pdo_is_read_from_its_own_register() {
+    // 28V fixed PDO
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceExtendedPowerRange8 as u8],
+        vec![0x8C, 0xA0],
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdo = device
+        .get_source_power_capability(PowerDataObject::ExtendedPowerRange8)
+        .unwrap();
+    assert!(matches!(pdo, SourcePowerRangeDataObject::Extended(_)));
+    assert!(pdo.is_detected());
+    assert_max_voltage(&pdo, 28000.0);
+    i2c.done();
+}
+
+#[test]
+fn missing_pdo_is_reported_as_not_detected() {
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceStandardPowerRange7 as u8],
+        vec![0x00, 0x00],
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdo = device
+        .get_source_power_capability(PowerDataObject::StandardPowerRange7)
+        .unwrap();
+    assert!(!pdo.is_detected());
+    i2c.done();
+}
+
+#[test]
+fn reserved_minimum_voltage_reports_the_power_data_object_register() {
+    // PPS APDO with the reserved minimum voltage code 0
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceStandardPowerRange4 as u8],
+        vec![0xD2, 0xFC],
+    )];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let pdo = device
+        .get_source_power_capability(PowerDataObject::StandardPowerRange4)
+        .unwrap();
+    assert_eq!(
+        pdo.get_min_voltage(),
+        Err(Ap33772sError::ConversionFailed {
+            command: Some(Command::AllSourcesPowerDataObject),
+            value: 0.0,
+        })
+    );
+    i2c.done();
+}
@@ -0,0 +1,142 @@
+//! Tests for reading all statistics registers in a single I2C transfer.
+//!
+//! ```bash
+//! cargo test --test test_statistics_burst_read
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::BurstRead;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::units::*;
+use common::{ADDRESS, assert_close, read};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// 20V, 2.4A, 30°C, 20V requested, 3A requested
+const VOLTAGE: [u8; 2] = [0xFA, 0x00];
+const CURRENT: [u8; 1] = [0x64];
+const TEMPERATURE: [u8; 1] = [0x1E];
+const REQUESTED_VOLTAGE: [u8; 2] = [0x90, 0x01];
+const REQUESTED_CURRENT: [u8; 2] = [0x2C, 0x01];
+
+fn burst_read(data: Vec<u8>) -> Transaction {
+    Transaction::write_read(ADDRESS, vec![Command::Voltage as u8], data)
+}
+
+fn auto_incremented_registers() -> Vec<u8> {
+    [
+        &VOLTAGE[..],
+        &CURRENT,
+        &TEMPERATURE,
+        &REQUESTED_VOLTAGE,
+        &REQUESTED_CURRENT,
+    ]
+    .concat()
+}
+
+fn individual_reads() -> Vec<Transaction> {
+    vec![
+        read(Command::Current, &CURRENT),
+        read(Command::Voltage, &VOLTAGE),
+        read(Command::Temperature, &TEMPERATURE),
+        read(Command::VoltageRequested, &REQUESTED_VOLTAGE),
+        read(Command::CurrentRequested, &REQUESTED_CURRENT),
+    ]
+}
+
+fn detection_reads() -> Vec<Transaction> {
+    vec![
+        read(Command::VoltageRequested, &REQUESTED_VOLTAGE),
+        read(Command::CurrentRequested, &REQUESTED_CURRENT),
+    ]
+}
+
+fn assert_expected_statistics(device: &mut Ap33772s<I2cMock, NoopDelay>) {
+    let statistics = device.get_statistics().unwrap();
+    assert_close(statistics.voltage.get::<millivolt>(), 20000.0);
+    assert_close(statistics.current.get::<milliampere>(), 2400.0);
+    assert_close(statistics.temperature.get::<degree_celsius>(), 30.0);
+    assert_close(statistics.requested_voltage.get::<millivolt>(), 20000.0);
+    assert_close(statistics.requested_current.get::<milliampere>(), 3000.0);
+}
+
+#[test]
+fn detected_burst_reads_are_used_for_later_snapshots() {
+    let mut expectations = vec![burst_read(auto_incremented_registers())];
+    expectations.extend(detection_reads());
+    expectations.push(burst_read(auto_incremented_registers()));
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_expected_statistics(&mut device);
+    assert_eq!(device.statistics_burst_read(), BurstRead::Enabled);
+    assert_expected_statistics(&mut device);
+    i2c.done();
+}
+
+#[test]
+fn repeated_register_falls_back_to_individual_reads() {
+    // A device without auto-increment keeps returning the VOLTAGE register
+    let repeated = VOLTAGE.repeat(4);
+    let mut expectations = vec![burst_read(repeated)];
+    expectations.extend(detection_reads());
+    expectations.extend(individual_reads());
+    expectations.extend(individual_reads());
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_expected_statistics(&mut device);
+    assert_eq!(device.statistics_burst_read(), BurstRead::Disabled);
+    assert_expected_statistics(&mut device);
+    i2c.done();
+}
+
+#[test]
+fn failed_burst_read_is_detected_again() {
+    let mut expectations = vec![
+        burst_read(vec![0x00; 8]).with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)),
+    ];
+    expectations.extend(individual_reads());
+    expectations.push(burst_read(auto_incremented_registers()));
+    expectations.extend(detection_reads());
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_expected_statistics(&mut device);
+    assert_eq!(device.statistics_burst_read(), BurstRead::Detect);
+    assert_expected_statistics(&mut device);
+    assert_eq!(device.statistics_burst_read(), BurstRead::Enabled);
+    i2c.done();
+}
+
+#[test]
+fn changing_measurements_do_not_disable_burst_reads() {
+    // The current and temperature change while the requested values stay with the contract
+    let mut registers = auto_incremented_registers();
+    registers[2] = CURRENT[0] + 1;
+    registers[3] = TEMPERATURE[0] + 1;
+    let mut expectations = vec![burst_read(registers)];
+    expectations.extend(detection_reads());
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let statistics = device.get_statistics().unwrap();
+    assert_close(statistics.temperature.get::<degree_celsius>(), 31.0);
+    assert_eq!(device.statistics_burst_read(), BurstRead::Enabled);
+    i2c.done();
+}
+
+#[test]
+fn enabled_burst_read_skips_detection() {
+    let expectations = [burst_read(auto_incremented_registers())];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_statistics_burst_read(BurstRead::Enabled);
+
+    assert_expected_statistics(&mut device);
+    i2c.done();
+}
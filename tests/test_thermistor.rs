@@ -0,0 +1,154 @@
+//! Tests deriving thermal resistances from thermistor models.
+//!
+//! These tests do not need any hardware and can be run with:
+//!
+//! ```bash
+//! cargo test --test test_thermistor
+//! ```
+use ap33772s_rs::thermistor::{ThermalModel, Thermistor, ThermistorModel};
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::types::{Ap33772sError, ThermalResistances};
+use ap33772s_rs::units::*;
+
+/// The Beta model only matches a thermistor's table exactly between the two temperatures its β
+/// value was measured at, so allow a few percent of error elsewhere.
+fn assert_close(actual: ElectricalResistance, expected: ElectricalResistance) {
+    let actual = actual.get::<ohm>();
+    let expected = expected.get::<ohm>();
+    assert!(
+        (actual - expected).abs() <= expected * 0.03,
+        "expected {expected} Ω, got {actual} Ω"
+    );
+}
+
+#[test]
+fn register_defaults_match_murata_ncp15xh103() {
+    let derived = ThermalResistances::from_thermistor(Thermistor::MurataNcp15xh103f03rc).unwrap();
+    let defaults = ThermalResistances::default();
+    assert_close(derived._25, defaults._25);
+    assert_close(derived._50, defaults._50);
+    assert_close(derived._75, defaults._75);
+    assert_close(derived._100, defaults._100);
+}
+
+#[test]
+fn resistances_above_register_range_are_rejected() {
+    let result = ThermalResistances::from_beta(ElectricalResistance::new::<ohm>(100_000.0), 4250.0);
+    assert_eq!(
+        result,
+        Err(Ap33772sError::ConversionFailed {
+            command: Some(Command::ThermalResistance25),
+            value: 100_000.0,
+        })
+    );
+}
+
+#[test]
+fn invalid_model_parameters_are_rejected() {
+    let result = ThermalResistances::from_beta(ElectricalResistance::new::<ohm>(-10.0), 3380.0);
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::ConversionFailed {
+            command: Some(Command::ThermalResistance25),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn steinhart_hart_round_trips() {
+    // Coefficients for a typical 10 kΩ thermistor
+    let model = ThermistorModel::SteinhartHart {
+        a: 1.009_249_5e-3,
+        b: 2.378_405e-4,
+        c: 2.019_202e-7,
+    };
+    for celsius in [25.0, 50.0, 75.0, 100.0] {
+        let resistance = model
+            .resistance_at(ThermodynamicTemperature::new::<degree_celsius>(celsius))
+            .unwrap();
+        let temperature = model.temperature_at(resistance).unwrap();
+        assert!((temperature.get::<degree_celsius>() - celsius).abs() < 0.05);
+    }
+    let resistances =
+        ThermalResistances::from_steinhart_hart(1.009_249_5e-3, 2.378_405e-4, 2.019_202e-7)
+            .unwrap();
+    assert!(resistances._25 > resistances._50);
+    assert!(resistances._75 > resistances._100);
+}
+
+fn celsius(value: f32) -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<degree_celsius>(value)
+}
+
+#[test]
+fn thermal_model_passes_through_table_points() {
+    let resistances = ThermalResistances::default();
+    let model = ThermalModel::new(&resistances).unwrap();
+    for (resistance, expected) in [
+        (resistances._25, 25.0),
+        (resistances._50, 50.0),
+        (resistances._75, 75.0),
+        (resistances._100, 100.0),
+    ] {
+        let temperature = model.temperature_at(resistance).get::<degree_celsius>();
+        assert!((temperature - expected).abs() < 0.01);
+        assert_close(model.resistance_at(celsius(expected)).unwrap(), resistance);
+    }
+}
+
+#[test]
+fn thermal_model_round_trips_between_points() {
+    let model = ThermalModel::new(&ThermalResistances::default()).unwrap();
+    for value in [10.0, 37.5, 62.0, 90.0, 110.0] {
+        let resistance = model.resistance_at(celsius(value)).unwrap();
+        let temperature = model.temperature_at(resistance).get::<degree_celsius>();
+        assert!((temperature - value).abs() < 0.01);
+    }
+}
+
+#[test]
+fn thermal_model_rejects_increasing_resistances() {
+    let mut resistances = ThermalResistances::default();
+    resistances._75 = resistances._50;
+    assert!(matches!(
+        ThermalModel::new(&resistances),
+        Err(Ap33772sError::ConversionFailed {
+            command: Some(Command::ThermalResistance75),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn error_band_is_zero_at_table_points_and_bounded_between() {
+    let thermistor = Thermistor::MurataNcp15xh103f03rc.model();
+    let resistances = ThermalResistances::from_model(&thermistor).unwrap();
+    let model = ThermalModel::new(&resistances).unwrap();
+
+    let at_point = model
+        .error_band(&thermistor, celsius(50.0), celsius(50.0))
+        .unwrap();
+    assert!(at_point.lowest.get::<kelvin>().abs() < 0.01);
+    assert!(at_point.highest.get::<kelvin>().abs() < 0.01);
+
+    // Linear interpolation of a convex curve always reads high between the points
+    let band = model
+        .error_band(&thermistor, celsius(25.0), celsius(100.0))
+        .unwrap();
+    assert!(band.lowest.get::<kelvin>() > -0.01);
+    assert!(band.highest.get::<kelvin>() > 0.5);
+    assert!(band.highest.get::<kelvin>() < 5.0);
+    let worst = band.worst_case_at.get::<degree_celsius>();
+    assert!((25.0..=50.0).contains(&worst));
+}
+
+#[test]
+fn error_band_rejects_reversed_range() {
+    let model = ThermalModel::new(&ThermalResistances::default()).unwrap();
+    let thermistor = Thermistor::MurataNcp15xh103f03rc.model();
+    assert!(matches!(
+        model.error_band(&thermistor, celsius(100.0), celsius(25.0)),
+        Err(Ap33772sError::ConversionFailed { command: None, .. })
+    ));
+}
@@ -0,0 +1,246 @@
+//! Tests for tracking the over voltage and over current thresholds to the negotiated contract.
+//!
+//! ```bash
+//! cargo test --test test_threshold_tracking
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::ThresholdTracking;
+use ap33772s_rs::types::command_structures::{
+    Command, OperatingCurrentSelection, PowerDataObject, PowerDeliveryResponse,
+};
+use common::{ADDRESS, threshold_snapshot};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+/// A source with a single detected 20V 3A fixed PDO
+fn source_capabilities() -> Transaction {
+    let mut capabilities = vec![0x00; 26];
+    capabilities[0] = 0xC8;
+    capabilities[1] = 0xA0;
+    Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        capabilities,
+    )
+}
+
+fn write_thresholds(over_voltage: u8, over_current: u8) -> Vec<Transaction> {
+    vec![
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverVoltageProtectionThreshold as u8, over_voltage],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::OverCurrentProtectionThreshold as u8, over_current],
+        ),
+    ]
+}
+
+/// Requests PDO1 at 3A and reads back the result
+fn request(result: u8) -> Vec<Transaction> {
+    vec![
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryRequestMessage as u8, 0x00, 0x18],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryMessageResult as u8],
+            vec![result],
+        ),
+    ]
+}
+
+fn negotiate(expectations: Vec<Transaction>) -> PowerDeliveryResponse {
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let response = device
+        .negotiate_power_delivery(
+            PowerDataObject::StandardPowerRange1,
+            None,
+            OperatingCurrentSelection::_3A,
+            &data_objects,
+        )
+        .unwrap();
+    i2c.done();
+    response
+}
+
+#[test]
+fn accepted_contract_tightens_thresholds() {
+    // Thresholds left over from a 5V 4A contract, 800mV OVP offset and 4A OCP
+    let mut expectations = vec![source_capabilities()];
+    expectations.extend(threshold_snapshot(0x0A, 0x50));
+    // 10% of 20V is a 2000mV offset, OCP stays at 4A until the 3A contract is accepted
+    expectations.extend(write_thresholds(0x19, 0x50));
+    expectations.extend(request(0x01));
+    expectations.extend([
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::VoltageRequested as u8],
+            vec![0x90, 0x01],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::CurrentRequested as u8],
+            vec![0x2C, 0x01],
+        ),
+    ]);
+    expectations.extend(write_thresholds(0x19, 0x3C));
+
+    assert_eq!(negotiate(expectations), PowerDeliveryResponse::Success);
+}
+
+#[test]
+fn rejected_contract_restores_thresholds() {
+    let mut expectations = vec![source_capabilities()];
+    expectations.extend(threshold_snapshot(0x0A, 0x50));
+    expectations.extend(write_thresholds(0x19, 0x50));
+    expectations.extend(request(0x03));
+    expectations.extend(write_thresholds(0x0A, 0x50));
+
+    assert_eq!(negotiate(expectations), PowerDeliveryResponse::NotSupported);
+}
+
+#[test]
+fn maximum_request_loosens_thresholds_before_it_is_sent() {
+    // Thresholds left over from a 5V 3A contract, 800mV OVP offset and 3A OCP
+    let mut expectations = vec![Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceStandardPowerRange1 as u8],
+        vec![0xC8, 0xA0],
+    )];
+    expectations.extend(threshold_snapshot(0x0A, 0x3C));
+    // Both are raised to the PDO maximum before the request, 5V + 800mV would trip at 20V. OCP
+    // is raised to 3.25A, the top of the 3A range the PDO reports.
+    expectations.extend(write_thresholds(0x19, 0x41));
+    expectations.extend([
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryRequestMessage as u8, 0xFF, 0x1F],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryMessageResult as u8],
+            vec![0x01],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::VoltageRequested as u8],
+            vec![0x90, 0x01],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::CurrentRequested as u8],
+            vec![0x2C, 0x01],
+        ),
+    ]);
+    expectations.extend(write_thresholds(0x19, 0x3C));
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+
+    let response = device
+        .negotiate_maximum_power_delivery(PowerDataObject::StandardPowerRange1)
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
+
+/// Reads the requested voltage and current of an accepted 20V 5A contract
+fn requested_20v_5a() -> Vec<Transaction> {
+    vec![
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::VoltageRequested as u8],
+            vec![0x90, 0x01],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::CurrentRequested as u8],
+            vec![0xF4, 0x01],
+        ),
+    ]
+}
+
+#[test]
+fn five_amp_contract_tracks_over_current_at_five_amps() {
+    let mut capabilities = vec![0x00; 26];
+    capabilities[0..2].copy_from_slice(&common::FIXED_20V_5A);
+    let mut expectations = vec![Transaction::write_read(
+        ADDRESS,
+        vec![Command::AllSourcesPowerDataObject as u8],
+        capabilities,
+    )];
+    expectations.extend(threshold_snapshot(0x0A, 0x50));
+    // The PDO reports 5A or more, OCP is raised to the 5A CURRENT_SEL can request
+    expectations.extend(write_thresholds(0x19, 0x64));
+    expectations.extend([
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryRequestMessage as u8, 0x00, 0x1F],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryMessageResult as u8],
+            vec![0x01],
+        ),
+    ]);
+    expectations.extend(requested_20v_5a());
+    expectations.extend(write_thresholds(0x19, 0x64));
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+
+    let data_objects = device.get_all_source_power_capabilities().unwrap();
+    let response = device
+        .negotiate_power_delivery(
+            PowerDataObject::StandardPowerRange1,
+            None,
+            OperatingCurrentSelection::Maximum,
+            &data_objects,
+        )
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
+
+#[test]
+fn five_amp_maximum_request_tracks_over_current_at_five_amps() {
+    let mut expectations = vec![Transaction::write_read(
+        ADDRESS,
+        vec![Command::SourceStandardPowerRange1 as u8],
+        common::FIXED_20V_5A.to_vec(),
+    )];
+    expectations.extend(threshold_snapshot(0x0A, 0x50));
+    expectations.extend(write_thresholds(0x19, 0x64));
+    expectations.extend([
+        Transaction::write(
+            ADDRESS,
+            vec![Command::PowerDeliveryRequestMessage as u8, 0xFF, 0x1F],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::PowerDeliveryMessageResult as u8],
+            vec![0x01],
+        ),
+    ]);
+    expectations.extend(requested_20v_5a());
+    expectations.extend(write_thresholds(0x19, 0x64));
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_threshold_tracking(Some(ThresholdTracking::default()));
+
+    let response = device
+        .negotiate_maximum_power_delivery(PowerDataObject::StandardPowerRange1)
+        .unwrap();
+    assert_eq!(response, PowerDeliveryResponse::Success);
+    i2c.done();
+}
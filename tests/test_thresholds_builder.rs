@@ -0,0 +1,132 @@
+//! Tests validating and rounding thresholds with the `Thresholds` builder.
+//!
+//! These tests do not need any hardware and can be run with:
+//!
+//! ```bash
+//! cargo test --test test_thresholds_builder
+//! ```
+mod common;
+
+use ap33772s_rs::types::{
+    Ap33772sError, Rounding, ThresholdError, ThresholdField, Thresholds, UnderVoltageThreshold,
+};
+use ap33772s_rs::units::*;
+use common::assert_close;
+
+fn celsius(value: f32) -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<degree_celsius>(value)
+}
+
+fn valid_builder() -> ap33772s_rs::types::ThresholdsBuilder {
+    Thresholds::builder()
+        .over_temperature(celsius(100.0))
+        .derating(celsius(90.0))
+}
+
+#[test]
+fn register_defaults_are_preserved() {
+    let thresholds = valid_builder().build().unwrap();
+    let defaults = Thresholds::default();
+    assert_eq!(thresholds.over_voltage, defaults.over_voltage);
+    assert_eq!(thresholds.over_current, defaults.over_current);
+    assert_eq!(thresholds.under_voltage, defaults.under_voltage);
+}
+
+#[test]
+fn values_are_rounded_with_the_selected_policy() {
+    let current = ElectricCurrent::new::<milliampere>(3230.0);
+    let rounded = |rounding| {
+        valid_builder()
+            .over_current(current)
+            .rounding(rounding)
+            .build()
+            .unwrap()
+            .over_current
+            .get::<milliampere>()
+    };
+    assert_close(rounded(Rounding::Down), 3200.0);
+    assert_close(rounded(Rounding::Nearest), 3250.0);
+    assert_close(rounded(Rounding::Up), 3250.0);
+
+    let thresholds = valid_builder()
+        .over_voltage(ElectricPotential::new::<millivolt>(1010.0))
+        .over_temperature(celsius(85.4))
+        .derating(celsius(75.0))
+        .under_voltage(UnderVoltageThreshold::SeventyPercent)
+        .rounding(Rounding::Up)
+        .build()
+        .unwrap();
+    assert_close(thresholds.over_voltage.get::<millivolt>(), 1040.0);
+    assert_close(thresholds.over_temperature.get::<degree_celsius>(), 86.0);
+    assert_eq!(
+        thresholds.under_voltage,
+        UnderVoltageThreshold::SeventyPercent
+    );
+}
+
+#[test]
+fn register_defaults_reject_equal_temperatures() {
+    assert_eq!(
+        Thresholds::builder().build(),
+        Err(Ap33772sError::InvalidThreshold(
+            ThresholdError::DeratingNotBelowOverTemperature
+        ))
+    );
+}
+
+#[test]
+fn derating_must_be_below_over_temperature() {
+    let error = valid_builder()
+        .derating(celsius(101.0))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        Ap33772sError::InvalidThreshold(ThresholdError::DeratingNotBelowOverTemperature)
+    );
+    let Ap33772sError::InvalidThreshold(error) = error else {
+        unreachable!()
+    };
+    assert_eq!(error.field(), ThresholdField::Derating);
+}
+
+#[test]
+fn zero_over_voltage_offset_is_rejected() {
+    assert_eq!(
+        valid_builder()
+            .over_voltage(ElectricPotential::new::<millivolt>(30.0))
+            .build(),
+        Err(Ap33772sError::InvalidThreshold(
+            ThresholdError::OverVoltageNotAboveRequestedVoltage
+        ))
+    );
+}
+
+#[test]
+fn unencodable_values_report_their_field() {
+    let out_of_range = |field| {
+        Err(Ap33772sError::InvalidThreshold(ThresholdError::OutOfRange(
+            field,
+        )))
+    };
+    assert_eq!(
+        valid_builder()
+            .over_current(ElectricCurrent::new::<milliampere>(12_800.0))
+            .build(),
+        out_of_range(ThresholdField::OverCurrent)
+    );
+    assert_eq!(
+        valid_builder()
+            .over_voltage(ElectricPotential::new::<volt>(-1.0))
+            .build(),
+        out_of_range(ThresholdField::OverVoltage)
+    );
+    assert_eq!(
+        valid_builder().over_temperature(celsius(300.0)).build(),
+        out_of_range(ThresholdField::OverTemperature)
+    );
+    assert_eq!(
+        valid_builder().derating(celsius(-5.0)).build(),
+        out_of_range(ThresholdField::Derating)
+    );
+}
@@ -0,0 +1,63 @@
+//! Tests for the undocumented VDCTHR and GPIO registers.
+//!
+//! ```bash
+//! cargo test --test test_undocumented_registers
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::Ap33772sError;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::units::*;
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+#[test]
+fn vdc_threshold_is_a_percentage() {
+    let expectations = [
+        Transaction::write_read(ADDRESS, vec![Command::Vdcthr as u8], vec![15]),
+        Transaction::write(ADDRESS, vec![Command::Vdcthr as u8, 10]),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let threshold = device.get_vdc_threshold().unwrap();
+    assert!((threshold.get::<percent>() - 15.0).abs() < 1e-3);
+    device
+        .set_vdc_threshold(Ratio::new::<percent>(10.4))
+        .unwrap();
+    i2c.done();
+}
+
+#[test]
+fn vdc_threshold_out_of_range_is_rejected_before_writing() {
+    let mut i2c = I2cMock::new(&[]);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let result = device.set_vdc_threshold(Ratio::new::<percent>(300.0));
+    assert!(matches!(
+        result,
+        Err(Ap33772sError::ConversionFailed {
+            command: Some(Command::Vdcthr),
+            ..
+        })
+    ));
+    i2c.done();
+}
+
+#[test]
+fn gpio_is_read_and_written_as_a_raw_byte() {
+    let expectations = [
+        Transaction::write_read(ADDRESS, vec![Command::Gpio as u8], vec![0x00]),
+        Transaction::write(ADDRESS, vec![Command::Gpio as u8, 0x01]),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    assert_eq!(device.get_gpio().unwrap(), 0x00);
+    device.set_gpio(0x01).unwrap();
+    i2c.done();
+}
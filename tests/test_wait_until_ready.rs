@@ -0,0 +1,89 @@
+//! Tests for polling STATUS until the device is ready.
+//!
+//! ```bash
+//! cargo test --test test_wait_until_ready
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::Ap33772sError;
+use ap33772s_rs::types::command_structures::Command;
+use common::{ADDRESS, NEW_POWER_DATA_OBJECT, read, read_status};
+use core::time::Duration;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+const STARTED: u8 = 0x01;
+const I2C_READY: u8 = 0x02;
+
+#[test]
+fn flags_from_separate_reads_are_accumulated() {
+    // STATUS is cleared on read, so each flag is only reported once
+    let expectations = [
+        read_status(STARTED),
+        read_status(0x00),
+        read_status(I2C_READY),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let status = device
+        .wait_until_ready(Duration::from_millis(100), false)
+        .unwrap();
+    assert!(status.started());
+    assert!(status.i2c_ready());
+    i2c.done();
+}
+
+#[test]
+fn new_power_data_objects_are_waited_for_when_required() {
+    let expectations = [
+        read_status(STARTED | I2C_READY),
+        read_status(NEW_POWER_DATA_OBJECT),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    let status = device
+        .wait_until_ready(Duration::from_millis(100), true)
+        .unwrap();
+    assert!(status.new_power_data_object());
+    i2c.done();
+}
+
+#[test]
+fn timeout_reports_the_flags_seen() {
+    // Reads at 0ms, 5ms and 10ms
+    let expectations = [read_status(STARTED), read_status(0x00), read_status(0x00)];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    match device.wait_until_ready(Duration::from_millis(10), false) {
+        Err(Ap33772sError::ReadyTimeout(status)) => {
+            assert!(status.started());
+            assert!(!status.i2c_ready());
+        }
+        other => panic!("expected a ready timeout, got {other:?}"),
+    }
+    i2c.done();
+}
+
+#[test]
+fn ready_timeout_after_hard_reset_is_reported() {
+    let mut expectations = vec![read(Command::SystemControl, &[0x10])];
+    // A running device does not report every boot flag, so it is hard reset
+    expectations.extend((0..=20).map(|_| read_status(0x00)));
+    expectations.push(Transaction::write(
+        ADDRESS,
+        vec![Command::PowerDeliveryCommandMessage as u8, 0x01],
+    ));
+    // The device never reports it is ready again
+    expectations.extend((0..=20).map(|_| read_status(0x00)));
+    let mut i2c = I2cMock::new(&expectations);
+
+    let result = Ap33772s::new_default(i2c.clone(), NoopDelay::new());
+    assert!(matches!(result, Err(Ap33772sError::ReadyTimeout(_))));
+    i2c.done();
+}
@@ -0,0 +1,82 @@
+//! Tests for the optional write verification of the configuration setters.
+//!
+//! ```bash
+//! cargo test --test test_write_verification
+//! ```
+#![cfg(feature = "sync")]
+
+mod common;
+
+use ap33772s_rs::Ap33772s;
+use ap33772s_rs::types::command_structures::Command;
+use ap33772s_rs::types::{Ap33772sError, ThermalResistances};
+use common::ADDRESS;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+#[test]
+fn verification_disabled_only_writes() {
+    let expectations = [
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance25 as u8, 0x10, 0x27],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance50 as u8, 0x41, 0x10],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance75 as u8, 0x88, 0x07],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance100 as u8, 0xCE, 0x03],
+        ),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+
+    device
+        .set_thermal_resistances(ThermalResistances::default())
+        .expect("Writes should succeed");
+    i2c.done();
+}
+
+#[test]
+fn verification_reports_mismatched_register() {
+    let expectations = [
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance25 as u8, 0x10, 0x27],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::ThermalResistance25 as u8],
+            vec![0x10, 0x27],
+        ),
+        Transaction::write(
+            ADDRESS,
+            vec![Command::ThermalResistance50 as u8, 0x41, 0x10],
+        ),
+        Transaction::write_read(
+            ADDRESS,
+            vec![Command::ThermalResistance50 as u8],
+            vec![0x00, 0x00],
+        ),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut device = Ap33772s::new(i2c.clone(), NoopDelay::new());
+    device.set_write_verification(true);
+
+    let result = device.set_thermal_resistances(ThermalResistances::default());
+    assert_eq!(
+        result,
+        Err(Ap33772sError::WriteVerificationFailed {
+            command: Command::ThermalResistance50,
+            expected: 0x1041,
+            actual: 0x0000,
+        })
+    );
+    i2c.done();
+}